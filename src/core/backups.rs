@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+use color_eyre::eyre::{Context, Result};
+use similar::{ChangeTag, TextDiff};
+
+/// The format of the timestamp in the name of a backup, as in `tordek.2023-11-04_18-30-12-250.yaml`.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S-%3f";
+
+/// The number of unchanged lines shown around each change in a backup diff.
+const DIFF_CONTEXT: usize = 2;
+
+/// A timestamped copy of a player file, kept in a `backups` folder beside it.
+#[derive(Clone)]
+pub struct Backup {
+    /// The backup file.
+    pub path: PathBuf,
+    /// When the player file was backed up.
+    pub time: NaiveDateTime,
+}
+
+/// Get the folder the backups of a player file are kept in.
+pub fn backup_dir(path: &Path) -> PathBuf {
+    path.with_file_name("backups")
+}
+
+/// Write a file by writing to a temporary file beside it, then renaming it over the original.
+///
+/// The file is never left half written, even if the app stops part way through the save.
+pub fn write_atomic(path: &Path, data: &str) -> Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(".{}.tmp", name));
+
+    let written = std::fs::File::create(&temp).and_then(|mut file| {
+        std::io::Write::write_all(&mut file, data.as_bytes())?;
+        file.sync_all()
+    });
+    written
+        .and_then(|_| std::fs::rename(&temp, path))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp);
+        })
+        .wrap_err_with(|| {
+            format!(
+                "failed to write file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })
+}
+
+/// Copy a player file into its backups folder before it is overwritten with `data`,
+/// then remove all but the `keep` most recent backups.
+///
+/// Nothing is backed up if `keep` is zero, the file doesn't exist yet or it already
+/// holds `data`.
+pub fn backup(path: &Path, data: &str, keep: usize) -> Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+    if std::fs::read_to_string(path).is_ok_and(|old| old == data) {
+        return Ok(());
+    }
+
+    let dir = backup_dir(path);
+    std::fs::create_dir_all(&dir).wrap_err_with(|| {
+        format!(
+            "failed to create backups folder '{}'",
+            dir.to_str().unwrap_or(&dir.to_string_lossy())
+        )
+    })?;
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    let time = Local::now().format(TIMESTAMP_FORMAT);
+    let target = dir.join(format!("{}.{}.{}", stem, time, ext));
+    std::fs::copy(path, &target).wrap_err_with(|| {
+        format!(
+            "failed to back up player file '{}'",
+            path.to_str().unwrap_or(&path.to_string_lossy())
+        )
+    })?;
+
+    for old in list(path).iter().skip(keep) {
+        std::fs::remove_file(&old.path).wrap_err_with(|| {
+            format!(
+                "failed to remove old backup '{}'",
+                old.path.to_str().unwrap_or(&old.path.to_string_lossy())
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Move the backups of a player file that has been moved from `old` to `new`, so they
+/// are named after the new file.
+pub fn rename(old: &Path, new: &Path) -> Result<()> {
+    let backups = list(old);
+    if backups.is_empty() {
+        return Ok(());
+    }
+
+    let dir = backup_dir(new);
+    std::fs::create_dir_all(&dir).wrap_err_with(|| {
+        format!(
+            "failed to create backups folder '{}'",
+            dir.to_str().unwrap_or(&dir.to_string_lossy())
+        )
+    })?;
+    let stem = new.file_stem().unwrap_or_default().to_string_lossy();
+    for backup in backups {
+        let ext = backup
+            .path
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let time = backup.time.format(TIMESTAMP_FORMAT);
+        let target = dir.join(format!("{}.{}.{}", stem, time, ext));
+        std::fs::rename(&backup.path, &target).wrap_err_with(|| {
+            format!(
+                "failed to move backup '{}'",
+                backup
+                    .path
+                    .to_str()
+                    .unwrap_or(&backup.path.to_string_lossy())
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// List the backups of a player file, most recent first.
+///
+/// Backups made while the file had another extension are included.
+pub fn list(path: &Path) -> Vec<Backup> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let Ok(files) = std::fs::read_dir(backup_dir(path)) else {
+        return vec![];
+    };
+
+    let mut backups: Vec<Backup> = files
+        .filter_map(|f| f.ok().map(|f| f.path()))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_owned();
+            let (backup_stem, time) = name.rsplit_once('.')?;
+            let time = NaiveDateTime::parse_from_str(time, TIMESTAMP_FORMAT).ok()?;
+            (backup_stem == stem).then_some(Backup { path, time })
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.time));
+    backups
+}
+
+/// Get the lines that differ between two versions of a player file, with a few
+/// unchanged lines around each change.
+///
+/// Removed lines start with `-`, added lines with `+`, and separate changes are split by `...`.
+pub fn diff(old: &str, new: &str) -> Vec<String> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut lines = vec![];
+    for (i, group) in diff.grouped_ops(DIFF_CONTEXT).iter().enumerate() {
+        if i > 0 {
+            lines.push(String::from("..."));
+        }
+        for change in group.iter().flat_map(|op| diff.iter_changes(op)) {
+            let sign = match change.tag() {
+                ChangeTag::Delete => '-',
+                ChangeTag::Insert => '+',
+                ChangeTag::Equal => ' ',
+            };
+            lines.push(format!("{} {}", sign, change.value().trim_end()));
+        }
+    }
+    lines
+}
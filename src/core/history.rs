@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use color_eyre::eyre::{Context, Result};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::Display;
+
+/// The most lookup entries kept in the recent history.
+const MAX_RECENT: usize = 50;
+
+/// The list a line of the history menu comes from.
+#[derive(Clone, Copy, Display, PartialEq)]
+pub enum HistoryKind {
+    /// An entry pinned to the player.
+    Pinned,
+    /// An entry bookmarked by the user.
+    Bookmark,
+    /// A recently opened entry.
+    Recent,
+}
+
+/// The lookup entries a user has opened recently and bookmarked, shared between players.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct History {
+    /// The names of recently opened entries, most recent first.
+    pub recent: Vec<String>,
+    /// The names of bookmarked entries, in the order they were added.
+    pub bookmarks: Vec<String>,
+}
+
+impl History {
+    /// Load the history from the given file, or start a new one if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = std::fs::read(path).wrap_err_with(|| {
+            format!(
+                "failed to load lookup history from file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+        let history = serde_yaml::from_slice(data.as_slice()).wrap_err_with(|| {
+            format!(
+                "failed to parse lookup history from file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+        Ok(history)
+    }
+
+    /// Write the history to the given file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data = serde_yaml::to_string(self)?;
+        std::fs::write(path, data).wrap_err_with(|| {
+            format!(
+                "failed to save lookup history to file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })
+    }
+
+    /// Move an entry to the front of the recent history.
+    pub fn record(&mut self, name: &str) {
+        self.recent.retain(|n| !n.eq_ignore_ascii_case(name));
+        self.recent.insert(0, name.to_owned());
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    /// Whether an entry is bookmarked.
+    pub fn is_bookmarked(&self, name: &str) -> bool {
+        self.bookmarks.iter().any(|n| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Bookmark an entry, or remove the bookmark if it already has one.
+    pub fn toggle_bookmark(&mut self, name: &str) {
+        if self.is_bookmarked(name) {
+            self.bookmarks.retain(|n| !n.eq_ignore_ascii_case(name));
+        } else {
+            self.bookmarks.push(name.to_owned());
+        }
+    }
+}
@@ -3,6 +3,12 @@ mod scroll_provider;
 /// App settings
 pub mod settings;
 
+/// Lookup history and bookmarks
+pub mod history;
+
+/// Atomic player saves and timestamped backups
+pub mod backups;
+
 use std::{
     path::{Path, PathBuf},
     rc::Rc,
@@ -10,12 +16,20 @@ use std::{
 
 use crate::{
     lookup::{Lookup, LookupEntry},
-    player::Player,
+    markup::{self, Link},
+    metadata::Metadata,
+    player::{
+        companion::{Companion, CompanionKind},
+        magic_items::{MagicItem, MAX_ATTUNED},
+        Player,
+    },
 };
-use color_eyre::eyre::{Report, Result};
+use color_eyre::eyre::{eyre, Report, Result, WrapErr};
 use strum_macros::Display;
 
 use self::{
+    backups::Backup,
+    history::{History, HistoryKind},
     scroll_provider::ScrollProvider,
     settings::{SaveFormat, Settings},
 };
@@ -54,9 +68,46 @@ pub enum Selected {
     FreeLookupSelect,
     /// The proficiency menu is showing
     Proficiency,
+    /// The class skill choice menu is showing
+    ClassSkills,
+    /// The languages and tool, weapon and armor proficiency menu is showing.
+    ///
+    /// The app index holds the category being shown.
+    Training,
+    /// The weapon attack menu is showing
+    Attacks,
+    /// The background suggestion menu for the current detail field is showing
+    Suggestions,
+    /// The companion menu is showing.
+    ///
+    /// The first line is the player, followed by each companion.
+    Companions,
+    /// The magic item menu is showing.
+    MagicItems,
+    /// The file menu is showing.
+    FileMenu,
+    /// The save as prompt is showing.
+    SaveAs,
+    /// The rename prompt is showing.
+    Rename,
+    /// The prompt for a player file to import into the characters folder is showing.
+    Import,
+    /// The prompt asking whether to save unsaved changes before the player is replaced
+    /// is showing, see `App::change_player()`.
+    UnsavedChanges,
+    /// The menu of backups to restore the player from is showing.
+    Backups,
     /// The load menu is showing
     Load,
-    /// The error popup is showing
+    /// The menu of pinned, bookmarked and recent lookup entries is showing.
+    History,
+    /// The homebrew lookup entry editor is showing.
+    ///
+    /// The popup scroll line is the field being edited.
+    Homebrew,
+    /// The unsaved edits prompt shown before closing the homebrew editor is showing.
+    HomebrewDiscard,
+    /// The error popup, or a message popup shown with `App::show_message()`, is showing
     Error,
 }
 
@@ -79,21 +130,37 @@ pub enum ControlType<'a> {
     CycleFn(fn(&mut App), fn(&mut App)),
 }
 
-#[derive(Clone, Copy, Default, Display)]
+/// The number of fields in the homebrew editor: the name, category, short description,
+/// homebrew tag and description.
+pub const HOMEBREW_FIELDS: usize = 5;
+
+#[derive(Clone, Copy, Default, Display, PartialEq)]
 pub enum Tab {
     #[default]
     Notes,
     Inventory,
     Spells,
+    Details,
 }
 
 pub enum LookupResult {
     Success(Rc<LookupEntry>),
     Completion(Vec<Rc<LookupEntry>>),
-    Files(Vec<String>),
+    /// Player files, each with a preview of the player.
+    Files(Vec<(PathBuf, String)>),
     Invalid(String),
 }
 
+/// A way of replacing the current player, which waits on the unsaved changes prompt.
+pub enum PlayerChange {
+    /// Start a new character.
+    New,
+    /// Load the player in the given file.
+    Load(PathBuf),
+    /// Import the player file at the given path, see `App::import_player()`.
+    Import(String),
+}
+
 /// A struct representing the current app state.
 #[derive(Default)]
 pub struct App {
@@ -105,21 +172,73 @@ pub struct App {
     pub editing: bool,
     /// The currently selected tab.
     pub current_tab: Tab,
-    /// The player path specified at startup, if it exists.
+    /// The detail field shown in the details tab.
+    pub detail_field: usize,
+    /// The companion shown in place of the player, if any.
+    pub companion: Option<usize>,
+    /// The file the player was loaded from or last saved to, if any.
     pub path: Option<PathBuf>,
+    /// The format the player is saved in, if chosen with save as instead of the settings.
+    format: Option<SaveFormat>,
+    /// The player as it was last loaded or saved, used to find unsaved changes.
+    saved_player: Option<String>,
+    /// Whether the player differed from `saved_player` when last checked, see
+    /// `App::check_unsaved_changes()`.
+    unsaved: bool,
+    /// The player's name when they were last loaded or saved, used to find renames.
+    saved_name: Option<String>,
+    /// The change waiting on the unsaved changes prompt, if it is showing.
+    pending_change: Option<PlayerChange>,
+    /// The text typed into the save as, rename or import prompt.
+    pub file_buffer: String,
+    /// The backups of the player's file shown in the restore menu, most recent first,
+    /// each with the changes restoring it would make.
+    pub backups: Vec<(Backup, Vec<String>)>,
     /// The most recent lookup result, if it exists.
     pub current_lookup: Option<LookupResult>,
     /// The current free lookup buffer.
     pub lookup_buffer: String,
+    /// The words of the most recent description search, highlighted when showing lookups.
+    pub search_terms: Vec<String>,
+    /// The category that free lookup results are limited to, if any.
+    pub lookup_category: Option<String>,
+    /// The links to other entries in the lookup entry being shown.
+    pub lookup_links: Vec<Link>,
+    /// The selected link in the lookup entry being shown, if any.
+    pub lookup_link: Option<usize>,
+    /// The previously shown lookup entries and their scroll positions, most recent last.
+    lookup_history: Vec<(Rc<LookupEntry>, u32)>,
+    /// The lookup entries the user has opened recently and bookmarked.
+    pub history: History,
+    /// The file the lookup history is saved to, if any.
+    history_path: Option<PathBuf>,
+    /// The entry being written in the homebrew editor.
+    pub homebrew_entry: LookupEntry,
+    /// The name of the entry in the homebrew editor before it was edited, if it already existed.
+    homebrew_original: Option<String>,
+    /// The entry in the homebrew editor as it was when the editor was opened, used to find
+    /// unsaved edits.
+    homebrew_opened: Option<String>,
     /// The current selected control.
     pub selected: Option<Selected>,
     /// The current selected index for certain controls.
     pub index: u32,
     /// The current error string, if it exists.
     pub error: Option<String>,
+    /// The title of the popup showing `error`, if it is a message rather than an error.
+    pub message_title: Option<String>,
+    /// The result of the most recent roll, if it exists.
+    pub last_roll: Option<String>,
+    /// Whether the most recent lookup entry is docked beside the tab panel.
+    pub docked: bool,
+    /// The lookup entry shown in the docked pane, if any.
+    pub docked_entry: Option<Rc<LookupEntry>>,
     tab_scroll_provider: ScrollProvider,
     popup_scroll_provider: ScrollProvider,
+    docked_scroll_provider: ScrollProvider,
     settings: Settings,
+    /// The file the settings were loaded from, if any.
+    settings_path: Option<PathBuf>,
 }
 
 impl App {
@@ -128,6 +247,7 @@ impl App {
         let mut app = Self::default();
         app.popup_scroll_mut().set_max(1);
         app.tab_scroll_mut().set_max(1);
+        app.mark_saved();
         app
     }
 
@@ -142,37 +262,342 @@ impl App {
     pub fn load_player(&mut self, path: PathBuf) -> Result<()> {
         self.player = Player::load(path.as_path())?;
         self.path = Some(path);
+        self.format = None;
+        self.companion = None;
         let len = self.current_tab().len() as u32;
         self.tab_scroll_mut().set_max(len);
+        self.mark_saved();
         Ok(())
     }
 
+    /// Remember the player as it is now as the last saved version.
+    fn mark_saved(&mut self) {
+        self.saved_player = serde_json::to_string(&self.player).ok();
+        self.saved_name = Some(self.player.name.clone());
+        self.unsaved = false;
+    }
+
+    /// Whether the player has changed since it was last loaded or saved.
+    ///
+    /// This is only as recent as the last call to `App::check_unsaved_changes()`, so it can
+    /// be used every frame without comparing the whole player each time.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved
+    }
+
+    /// Compare the player with the last saved version, after anything that may have
+    /// changed them, such as a key press.
+    pub fn check_unsaved_changes(&mut self) {
+        self.unsaved = serde_json::to_string(&self.player).ok() != self.saved_player;
+    }
+
+    /// Get the format the player is saved in.
+    fn save_format(&self) -> SaveFormat {
+        self.format.unwrap_or(self.settings.format)
+    }
+
+    /// Get the file the player will be saved to.
+    ///
+    /// This is the file the player was loaded from, with the extension of the save
+    /// format. If that file is named after the player and the player has been renamed
+    /// since, the file is named after the new name instead. Players that have never
+    /// been saved are saved to a new file in the characters folder named after the player,
+    /// which fails if the name has nothing that can be used in a file name.
+    pub fn save_path(&self) -> Result<PathBuf> {
+        let ext = match self.save_format() {
+            SaveFormat::JSON => "json",
+            SaveFormat::YAML => "yaml",
+        };
+
+        let name = file_stem(&self.player.name);
+        let Some(path) = self.path.as_ref() else {
+            let Some(name) = name else {
+                return Err(eyre!(
+                    "the player needs a name before they can be saved, rename them in the file menu"
+                ));
+            };
+            let dir = self.settings.characters_dir();
+            return Ok(dir.join(format!("{}.{}", name, ext)));
+        };
+        // Keep the shorter spelling of YAML files rather than saving beside them
+        let ext = match (ext, path.extension().and_then(|e| e.to_str())) {
+            ("yaml", Some("yml")) => "yml",
+            _ => ext,
+        };
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let saved_stem = self.saved_name.as_deref().and_then(file_stem);
+        let stem = match name {
+            Some(name) if saved_stem.is_some_and(|saved| saved == stem && saved != name) => name,
+            _ => stem.into_owned(),
+        };
+        Ok(path.with_file_name(format!("{}.{}", stem, ext)))
+    }
+
     /// Saves the currently edited player.
     ///
     /// Will either save to the current player path or,
-    /// if it doesn't exist, a new file with the same name as the player.
-    pub fn save_player(&self) -> Result<()> {
-        let data = match self.settings.format {
+    /// if it doesn't exist, a new file in the characters folder with the same name as the player. When the
+    /// player's file is named after them and they have been renamed, the file is moved,
+    /// see `App::save_path()`. Neither a new file nor a moved one replaces an existing file.
+    pub fn save_player(&mut self) -> Result<()> {
+        if self.path.is_none() {
+            self.characters_dir()?;
+        }
+        let path = self.save_path()?;
+        if self.path.is_none() && path.exists() {
+            return Err(eyre!(
+                "a player file named '{}' already exists in the characters folder, rename the player or use save as",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ));
+        }
+        let renamed = self.path.as_ref().filter(|old| {
+            old.file_stem() != path.file_stem() && old.with_file_name("") == path.with_file_name("")
+        });
+        if renamed.is_some() && path.exists() {
+            return Err(eyre!(
+                "cannot rename the player's file to '{}' because it already exists",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            ));
+        }
+        let renamed = renamed.filter(|old| old.exists()).cloned();
+
+        self.write_player(&path)?;
+        if let Some(old) = &renamed {
+            std::fs::remove_file(old).wrap_err_with(|| {
+                format!(
+                    "failed to remove the old player file '{}'",
+                    old.to_str().unwrap_or(&old.to_string_lossy())
+                )
+            })?;
+        }
+        self.path = Some(path.clone());
+        self.mark_saved();
+        // The backups follow the file, so they can still be restored
+        match renamed {
+            Some(old) => backups::rename(&old, &path),
+            None => Ok(()),
+        }
+    }
+
+    /// Save the player if they have changed since they were last loaded or saved.
+    ///
+    /// New players without a name have nowhere to be saved yet, so they are skipped
+    /// rather than reporting an error every time.
+    pub fn autosave(&mut self) -> Result<()> {
+        let unnamed = self.path.is_none() && file_stem(&self.player.name).is_none();
+        if !self.has_unsaved_changes() || unnamed {
+            return Ok(());
+        }
+        self.save_player()
+    }
+
+    /// Save the player to a new file, which is used for every later save.
+    ///
+    /// The format is taken from the extension, which is added in the format from the
+    /// settings if there isn't one. Files other than the player's own are never replaced.
+    pub fn save_player_as(&mut self, path: &str) -> Result<()> {
+        let mut path = PathBuf::from(path.trim());
+        if path.as_os_str().is_empty() {
+            return Err(eyre!("no file given to save the player to"));
+        }
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("json" | "player") => SaveFormat::JSON,
+            Some("yaml" | "yml") => SaveFormat::YAML,
+            Some(ext) => return Err(eyre!("cannot save players as '.{}' files", ext)),
+            None => {
+                path.set_extension(match self.settings.format {
+                    SaveFormat::JSON => "json",
+                    SaveFormat::YAML => "yaml",
+                });
+                self.settings.format
+            }
+        };
+
+        // Replacing another file would lose it without a backup of the player it held
+        let current = self
+            .path
+            .as_ref()
+            .and_then(|p| std::fs::canonicalize(p).ok());
+        if path.exists() && std::fs::canonicalize(&path).ok() != current {
+            return Err(eyre!(
+                "'{}' already exists, choose another file to save the player to",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            ));
+        }
+
+        self.format = Some(format);
+        self.write_player(&path)?;
+        self.path = Some(path);
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Rename the player, moving their file if it is named after them.
+    ///
+    /// Players that have never been saved are only renamed.
+    pub fn rename_player(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(eyre!("the player needs a name"));
+        }
+        let old = std::mem::replace(&mut self.player.name, name.to_owned());
+        if self.path.is_none() {
+            return Ok(());
+        }
+        // Keep the old name if the file could not be moved
+        self.save_player().inspect_err(|_| self.player.name = old)
+    }
+
+    /// Start a new character, forgetting the current player and their file.
+    pub fn new_character(&mut self) {
+        self.player = Player::default();
+        self.path = None;
+        self.format = None;
+        self.companion = None;
+        self.tab_scroll_mut().reset();
+        let len = self.current_tab().len() as u32;
+        self.tab_scroll_mut().set_max(len);
+        self.mark_saved();
+        self.selected = None;
+    }
+
+    /// Replace the player as described by `change`.
+    ///
+    /// If the player has unsaved changes, the change waits while the user is asked
+    /// whether to save them first, see `App::finish_player_change()`.
+    pub fn change_player(&mut self, change: PlayerChange) -> Result<()> {
+        if self.has_unsaved_changes() {
+            self.pending_change = Some(change);
+            self.selected = Some(Selected::UnsavedChanges);
+            return Ok(());
+        }
+        self.apply_player_change(change)
+    }
+
+    /// Make the change waiting on the unsaved changes prompt, saving the player first
+    /// if `save` is set.
+    pub fn finish_player_change(&mut self, save: bool) -> Result<()> {
+        self.selected = None;
+        let Some(change) = self.pending_change.take() else {
+            return Ok(());
+        };
+        if save {
+            self.save_player()?;
+        }
+        self.apply_player_change(change)
+    }
+
+    /// Forget the change waiting on the unsaved changes prompt, keeping the player.
+    pub fn cancel_player_change(&mut self) {
+        self.pending_change = None;
+        self.selected = None;
+    }
+
+    fn apply_player_change(&mut self, change: PlayerChange) -> Result<()> {
+        match change {
+            PlayerChange::New => self.new_character(),
+            PlayerChange::Load(path) => {
+                self.load_player(path)?;
+                self.tab_scroll_mut().reset();
+                self.update_tab(Tab::Notes);
+            }
+            PlayerChange::Import(path) => self.import_player(&path)?,
+        }
+        Ok(())
+    }
+
+    /// Write the player to the given file in the current save format.
+    ///
+    /// The file is backed up first and replaced in one step, see `backups::write_atomic()`.
+    fn write_player(&self, path: &Path) -> Result<()> {
+        let data = match self.save_format() {
             SaveFormat::JSON => serde_json::to_string(&self.player)?,
             SaveFormat::YAML => serde_yaml::to_string(&self.player)?,
         };
+        backups::backup(path, &data, self.settings.backups)?;
+        backups::write_atomic(path, &data)
+    }
 
-        let path = match self.path.as_ref() {
-            Some(path) => path
-                .file_stem()
-                .unwrap()
-                .to_str()
-                .unwrap_or_default()
-                .to_owned(),
-            None => self.player.name.to_owned(),
+    /// Show the backups of the player's file, with the changes restoring each would make.
+    pub fn show_backups(&mut self) -> Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Err(eyre!(
+                "the player has not been saved yet, so has no backups"
+            ));
         };
+        let backups = backups::list(path);
+        if backups.is_empty() {
+            return Err(eyre!(
+                "there are no backups of '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            ));
+        }
 
-        let ext = match self.settings.format {
-            SaveFormat::JSON => "json",
-            SaveFormat::YAML => "yaml",
-        };
+        // Compare as YAML so that JSON files, which are saved on one line, diff by field
+        let current = serde_yaml::to_string(&self.player)?;
+        self.backups = backups
+            .into_iter()
+            .map(|backup| {
+                let diff = match Player::load(&backup.path) {
+                    Ok(player) => {
+                        let old = serde_yaml::to_string(&player).unwrap_or_default();
+                        backups::diff(&current, &old)
+                    }
+                    Err(e) => vec![format!("{:#}", e)],
+                };
+                (backup, diff)
+            })
+            .collect();
 
-        std::fs::write(format!("{}.{}", path, ext), data)?;
+        self.selected = Some(Selected::Backups);
+        self.index = 0;
+        self.popup_scroll_provider.reset();
+        self.popup_scroll_provider
+            .set_max(self.backups.len() as u32);
+        Ok(())
+    }
+
+    /// Get the lines of the restore menu, which show when each backup was made.
+    pub fn backup_lines(&self) -> Vec<String> {
+        self.backups
+            .iter()
+            .map(|(backup, diff)| {
+                let time = backup.time.format("%Y-%m-%d %H:%M:%S");
+                if diff.is_empty() {
+                    format!("{} (unchanged)", time)
+                } else {
+                    time.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Get the changes restoring the selected backup would make.
+    pub fn backup_diff(&self) -> &[String] {
+        let line = self.popup_scroll_provider.get_line() as usize;
+        self.backups
+            .get(line)
+            .map(|(_, diff)| diff.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Replace the player with the selected backup and write it to the player's file.
+    ///
+    /// The file is kept where it is, even if the backup has an older name, and is backed
+    /// up before it is overwritten, so a restore can be undone.
+    pub fn restore_backup(&mut self) -> Result<()> {
+        let line = self.popup_scroll_provider.get_line() as usize;
+        let (Some(path), Some((backup, _))) = (self.path.clone(), self.backups.get(line)) else {
+            return Ok(());
+        };
+        self.player = Player::load(&backup.path)?;
+        self.companion = None;
+        self.backups.clear();
+        self.selected = None;
+        let len = self.current_tab().len() as u32;
+        self.tab_scroll_mut().set_max(len);
+        self.write_player(&path)?;
+        self.mark_saved();
         Ok(())
     }
 
@@ -188,10 +613,17 @@ impl App {
 
     pub fn show_error(&mut self, error: String) {
         self.error = Some(error);
+        self.message_title = None;
         self.selected = Some(Selected::Error);
         self.popup_scroll_provider.clear_max();
     }
 
+    /// Show a report that is not an error in a popup with the given title.
+    pub fn show_message(&mut self, title: &str, message: String) {
+        self.show_error(message);
+        self.message_title = Some(title.to_owned());
+    }
+
     /// Returns a reference to the tab scroll provider
     pub fn tab_scroll(&self) -> &ScrollProvider {
         &self.tab_scroll_provider
@@ -212,6 +644,16 @@ impl App {
         &mut self.popup_scroll_provider
     }
 
+    /// Returns a reference to the docked lookup scroll provider
+    pub fn docked_scroll(&self) -> &ScrollProvider {
+        &self.docked_scroll_provider
+    }
+
+    /// Returns a mutable reference to the docked lookup scroll provider
+    pub fn docked_scroll_mut(&mut self) -> &mut ScrollProvider {
+        &mut self.docked_scroll_provider
+    }
+
     /// Returns a reference to the app settings
     pub fn settings(&self) -> &Settings {
         &self.settings
@@ -223,26 +665,71 @@ impl App {
     pub fn load_settings(&mut self, path: &Path) -> Option<Report> {
         let (s, err) = Settings::load_or_default(path);
         self.settings = s;
+        self.settings_path = Some(path.to_path_buf());
         err
     }
 
+    /// Load the app settings again from the file they were loaded from.
+    ///
+    /// On failure, the current settings are kept.
+    pub fn reload_settings(&mut self) -> Result<()> {
+        if let Some(path) = &self.settings_path {
+            self.settings = Settings::load(path)?;
+        }
+        Ok(())
+    }
+
+    /// Reload every lookup file and the app settings, keeping the open player.
+    pub fn reload(&mut self, lookup: &mut Lookup) -> Result<()> {
+        lookup.reload()?;
+        self.reload_settings()
+    }
+
+    /// Attempt to load the lookup history from the given file, which it will be saved to
+    /// from now on.
+    ///
+    /// On failure, starts with an empty history that is not saved, so the file is left as is.
+    pub fn load_history(&mut self, path: &Path) -> Result<()> {
+        self.history = History::load(path)?;
+        self.history_path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Save the lookup history to the file it was loaded from.
+    fn save_history(&self) -> Result<()> {
+        match &self.history_path {
+            Some(path) => self.history.save(path),
+            None => Ok(()),
+        }
+    }
+
     /// Returns a reference to the data of the currently selected tab.
+    ///
+    /// While a companion is shown, this is always the companion's attacks.
     pub fn current_tab(&self) -> &Vec<String> {
-        use Tab::{Inventory, Notes, Spells};
+        use Tab::{Details, Inventory, Notes, Spells};
+        if let Some(idx) = self.companion {
+            return &self.player.companions[idx].attacks;
+        }
         match self.current_tab {
             Notes => &self.player.notes,
             Inventory => &self.player.inventory,
             Spells => &self.player.spells,
+            Details => self.player.details.nth(self.detail_field),
         }
     }
 
     /// Returns a mutable reference to the data of the currently selected tab.
     pub fn current_tab_mut(&mut self) -> &mut Vec<String> {
-        use Tab::{Inventory, Notes, Spells};
+        use Tab::{Details, Inventory, Notes, Spells};
+        if let Some(idx) = self.companion {
+            return &mut self.player.companions[idx].attacks;
+        }
         match self.current_tab {
             Notes => &mut self.player.notes,
             Inventory => &mut self.player.inventory,
             Spells => &mut self.player.spells,
+            Details => self.player.details.nth_mut(self.detail_field),
         }
     }
 
@@ -252,7 +739,7 @@ impl App {
         self.tab_scroll_provider
             .set_max(self.current_tab().len() as u32);
         let line = self.tab_scroll_provider.get_line() as usize;
-        if self.current_tab().len() > 0 {
+        if !self.current_tab().is_empty() {
             self.index = self.index.min(self.current_tab()[line].len() as u32);
         } else {
             self.index = 0;
@@ -360,48 +847,626 @@ impl App {
 
     /// Try to lookup the given text
     fn lookup_text(&mut self, lookup: &mut Lookup, text: &str) -> Result<()> {
-        let lookup = lookup.get_entry(text);
+        self.search_terms.clear();
+        self.lookup_history.clear();
+        self.find_entry(lookup, text)
+    }
 
+    /// Show the entry with the given name, or a failed lookup if there is none.
+    fn find_entry(&mut self, lookup: &mut Lookup, text: &str) -> Result<()> {
         // Probably shouldn't clone but the lifetimes were too confusing :(
-        self.current_lookup = match lookup? {
-            Some(entry) => {
-                self.popup_scroll_provider.clear_max();
-                Some(LookupResult::Success(entry.clone()))
-            }
+        match lookup.get_entry(text)?.cloned() {
+            Some(entry) => self.show_entry(entry, lookup)?,
             None => {
                 self.popup_scroll_provider.set_max(1);
-                Some(LookupResult::Invalid(text.to_owned()))
+                self.current_lookup = Some(LookupResult::Invalid(text.to_owned()));
+                self.lookup_links.clear();
+                self.lookup_link = None;
+            }
+        };
+
+        self.popup_scroll_provider.reset();
+        Ok(())
+    }
+
+    /// Show the given lookup entry, starting a new link history.
+    pub fn open_entry(&mut self, entry: Rc<LookupEntry>, lookup: &mut Lookup) -> Result<()> {
+        self.lookup_history.clear();
+        self.show_entry(entry, lookup)?;
+        self.popup_scroll_provider.reset();
+        Ok(())
+    }
+
+    /// Show the given lookup entry and find the links to other entries in it.
+    ///
+    /// Links written with `[[link]]` markup are always kept. Other entries are linked
+    /// at their first mention, except for the entry itself.
+    fn show_entry(&mut self, entry: Rc<LookupEntry>, lookup: &mut Lookup) -> Result<()> {
+        let (text, mut links) = markup::render(&entry.full_text());
+        let own = entry.name.to_lowercase();
+        let mut seen: Vec<String> = links.iter().map(|l| l.target.to_lowercase()).collect();
+
+        for (i, line) in text.lines.iter().enumerate() {
+            for (range, target) in lookup.find_references(&markup::plain(line))? {
+                let overlaps = links
+                    .iter()
+                    .any(|l| l.line == i && l.range.start < range.end && range.start < l.range.end);
+                if target == own || overlaps || seen.contains(&target) {
+                    continue;
+                }
+                seen.push(target.clone());
+                links.push(Link {
+                    target,
+                    line: i,
+                    range,
+                });
+            }
+        }
+        links.sort_by_key(|l| (l.line, l.range.start));
+
+        self.popup_scroll_provider.clear_max();
+        if !self
+            .docked_entry
+            .as_ref()
+            .is_some_and(|e| Rc::ptr_eq(e, &entry))
+        {
+            self.docked_entry = Some(entry.clone());
+            self.docked_scroll_provider.reset();
+        }
+        self.history.record(&entry.name);
+        self.current_lookup = Some(LookupResult::Success(entry));
+        self.lookup_links = links;
+        self.lookup_link = None;
+        self.save_history()
+    }
+
+    /// Dock the lookup entry being shown beside the tab panel and close the lookup popup.
+    pub fn dock_lookup(&mut self) {
+        self.docked = true;
+        self.current_lookup = None;
+        self.selected = match self.selected {
+            Some(Selected::ItemLookup(_)) => Some(Selected::TabItem),
+            _ => None,
+        };
+    }
+
+    /// Get the name of the lookup entry being shown, if any.
+    fn shown_entry(&self) -> Option<String> {
+        match &self.current_lookup {
+            Some(LookupResult::Success(entry)) => Some(entry.name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Bookmark the lookup entry being shown, or remove its bookmark.
+    pub fn toggle_bookmark(&mut self) -> Result<()> {
+        if let Some(name) = self.shown_entry() {
+            self.history.toggle_bookmark(&name);
+            self.save_history()?;
+        }
+        Ok(())
+    }
+
+    /// Pin the lookup entry being shown to the player, or unpin it.
+    pub fn toggle_pin(&mut self) {
+        if let Some(name) = self.shown_entry() {
+            let pinned = &mut self.player.pinned;
+            if pinned.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                pinned.retain(|n| !n.eq_ignore_ascii_case(&name));
+            } else {
+                pinned.push(name);
             }
+        }
+    }
+
+    /// Add the monster being shown as a companion of the given kind, such as a wild shape form.
+    pub fn add_monster_companion(&mut self, kind: CompanionKind) {
+        let Some(LookupResult::Success(entry)) = &self.current_lookup else {
+            return;
         };
+        let entry = entry.clone();
+        match &entry.metadata {
+            Some(Metadata::Monster(monster)) => {
+                let companion = Companion::from_monster(&entry.name, kind, monster);
+                self.player.companions.push(companion);
+            }
+            _ => self.show_error(format!("'{}' has no stat block", entry.name)),
+        }
+    }
+
+    /// Get every line of the history menu: the player's pinned entries, then the
+    /// bookmarks, then the recently opened entries.
+    pub fn history_lines(&self) -> Vec<(HistoryKind, String)> {
+        let pinned = self.player.pinned.iter().map(|n| (HistoryKind::Pinned, n));
+        let bookmarks = self
+            .history
+            .bookmarks
+            .iter()
+            .map(|n| (HistoryKind::Bookmark, n));
+        let recent = self.history.recent.iter().map(|n| (HistoryKind::Recent, n));
+        pinned
+            .chain(bookmarks)
+            .chain(recent)
+            .map(|(kind, name)| (kind, name.clone()))
+            .collect()
+    }
 
+    /// Open the menu of pinned, bookmarked and recent lookup entries.
+    pub fn show_history(&mut self) {
+        let len = self.history_lines().len() as u32;
         self.popup_scroll_provider.reset();
+        self.popup_scroll_provider.set_max(len);
+        self.selected = Some(Selected::History);
+    }
+
+    /// Open the lookup entry selected in the history menu.
+    pub fn open_history_line(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let line = self.popup_scroll_provider.get_line() as usize;
+        if let Some((_, name)) = self.history_lines().get(line) {
+            self.lookup_text(lookup, name)?;
+            self.selected = Some(Selected::ClassLookup);
+        }
         Ok(())
     }
 
-    /// Lookup all player files in the cwd
+    /// Remove the line selected in the history menu from its list.
+    pub fn remove_history_line(&mut self) -> Result<()> {
+        let line = self.popup_scroll_provider.get_line() as usize;
+        let Some((kind, name)) = self.history_lines().get(line).cloned() else {
+            return Ok(());
+        };
+        match kind {
+            HistoryKind::Pinned => self.player.pinned.retain(|n| *n != name),
+            HistoryKind::Bookmark => self.history.bookmarks.retain(|n| *n != name),
+            HistoryKind::Recent => self.history.recent.retain(|n| *n != name),
+        }
+        let len = self.history_lines().len() as u32;
+        self.popup_scroll_provider.set_max(len);
+        self.save_history()
+    }
+
+    /// Select the next link in the lookup entry being shown, or the previous one if `forward` is false.
+    pub fn select_link(&mut self, forward: bool) {
+        let count = self.lookup_links.len();
+        if count == 0 {
+            return;
+        }
+
+        let link = match (self.lookup_link, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(link), true) => (link + 1) % count,
+            (Some(link), false) => (link + count - 1) % count,
+        };
+        self.lookup_link = Some(link);
+        let row = self.link_row(&self.lookup_links[link]);
+        self.popup_scroll_provider.show_line(row);
+    }
+
+    /// Get the row a link in the lookup entry being shown ends on, once the entry has
+    /// been word wrapped to the width of the popup.
+    fn link_row(&self, link: &Link) -> u32 {
+        let Some(LookupResult::Success(entry)) = &self.current_lookup else {
+            return link.line as u32;
+        };
+        let (text, _) = markup::render(&entry.full_text());
+        let width = self.popup_scroll_provider.get_frame_width();
+        let above: u32 = text.lines[..link.line]
+            .iter()
+            .map(|l| markup::wrapped_rows(&markup::plain(l), width))
+            .sum();
+        let line = markup::plain(&text.lines[link.line]);
+        above + markup::wrapped_rows(&line[..link.range.end], width) - 1
+    }
+
+    /// Open the selected link, remembering the current entry so it can be returned to.
+    pub fn follow_link(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let Some(link) = self.lookup_link else {
+            return Ok(());
+        };
+        let target = self.lookup_links[link].target.clone();
+
+        if let Some(LookupResult::Success(entry)) = &self.current_lookup {
+            self.lookup_history
+                .push((entry.clone(), self.popup_scroll_provider.get_scroll()));
+        }
+        self.find_entry(lookup, &target)
+    }
+
+    /// Return to the previously shown lookup entry, if any.
+    pub fn lookup_back(&mut self, lookup: &mut Lookup) -> Result<()> {
+        if let Some((entry, scroll)) = self.lookup_history.pop() {
+            self.show_entry(entry, lookup)?;
+            self.popup_scroll_provider.scroll_to(scroll);
+        }
+        Ok(())
+    }
+
+    /// Open the homebrew editor with a new, empty entry.
+    pub fn new_homebrew(&mut self) {
+        self.homebrew_entry = LookupEntry {
+            homebrew: true,
+            ..Default::default()
+        };
+        self.homebrew_original = None;
+        self.open_homebrew_editor();
+    }
+
+    /// Open the homebrew editor with a copy of the lookup entry being shown, if any.
+    pub fn edit_homebrew(&mut self) {
+        let Some(LookupResult::Success(entry)) = &self.current_lookup else {
+            return;
+        };
+        self.homebrew_entry = entry.as_ref().clone();
+        self.homebrew_original = Some(entry.name.clone());
+        self.current_lookup = None;
+        self.open_homebrew_editor();
+    }
+
+    /// Whether the control being edited takes several lines, so enter starts a new line.
+    pub fn editing_multiline(&self) -> bool {
+        self.selected == Some(Selected::Homebrew) && self.popup_scroll_provider.get_line() == 4
+    }
+
+    /// Whether the entry in the homebrew editor has changed since the editor was opened.
+    pub fn has_unsaved_homebrew(&self) -> bool {
+        serde_json::to_string(&self.homebrew_entry).ok() != self.homebrew_opened
+    }
+
+    fn open_homebrew_editor(&mut self) {
+        self.homebrew_opened = serde_json::to_string(&self.homebrew_entry).ok();
+        self.popup_scroll_provider.reset();
+        self.popup_scroll_provider.set_max(HOMEBREW_FIELDS as u32);
+        self.selected = Some(Selected::Homebrew);
+    }
+
+    /// Save the entry in the homebrew editor to the homebrew lookup file and show it.
+    pub fn save_homebrew(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let entry = self.homebrew_entry.clone();
+        let name = entry.name.trim().to_owned();
+        lookup.save_homebrew(self.homebrew_original.as_deref(), entry)?;
+        self.lookup_text(lookup, &name)?;
+        self.selected = Some(Selected::ClassLookup);
+        Ok(())
+    }
+
+    /// Open the class skill choice menu for the player's current class.
+    pub fn choose_class_skills(&mut self) {
+        let (_, choices) = self.player.class.skill_choices(self.player.ruleset);
+        self.popup_scroll_provider.reset();
+        self.popup_scroll_provider.set_max(choices.len() as u32);
+        self.selected = Some(Selected::ClassSkills);
+    }
+
+    /// Switch the category shown in the training menu and reset its scroll.
+    pub fn update_training_category(&mut self, category: u32) {
+        self.index = category;
+        let len = self.player.proficiencies.nth(category).len() as u32;
+        self.popup_scroll_provider.reset();
+        self.popup_scroll_provider.set_max(len);
+        self.last_roll = None;
+    }
+
+    /// Show the given detail field in the details tab.
+    pub fn update_detail_field(&mut self, field: usize) {
+        self.detail_field = field;
+        self.tab_scroll_provider.reset();
+        self.update_tab(Tab::Details);
+    }
+
+    /// Get the background suggestions for the current detail field, if it has any.
+    pub fn detail_suggestions(&self) -> Option<&'static [&'static str]> {
+        self.player.background.detail_suggestions(self.detail_field)
+    }
+
+    /// Write a suggestion into the current detail field.
+    ///
+    /// The suggestion replaces the selected line if it is empty, otherwise it is added after it.
+    pub fn add_suggestion(&mut self, suggestion: &str) {
+        let line = self.tab_scroll_provider.get_line() as usize;
+        if self.current_tab().get(line).is_none_or(|l| !l.is_empty()) {
+            self.append_item_to_tab();
+        }
+
+        let line = self.tab_scroll_provider.get_line() as usize;
+        self.current_tab_mut()[line] = suggestion.to_string();
+        self.index = 0;
+    }
+
+    /// Get the companion shown in place of the player, if any.
+    pub fn current_companion(&self) -> Option<&Companion> {
+        self.companion.map(|idx| &self.player.companions[idx])
+    }
+
+    /// Show the given companion in place of the player, or the player if `None`.
+    pub fn view_companion(&mut self, companion: Option<usize>) {
+        self.companion = companion;
+        self.tab_scroll_provider.reset();
+        self.update_tab(self.current_tab);
+    }
+
+    /// Get the lines of the companion menu.
+    pub fn companion_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} (player)", self.player.name)];
+        for (idx, companion) in self.player.companions.iter().enumerate() {
+            let active = if self.player.wild_shape == Some(idx) {
+                " [active]"
+            } else {
+                ""
+            };
+            lines.push(format!("{} ({}){}", companion.name, companion.kind, active));
+        }
+        lines
+    }
+
+    /// Enter or leave the given wild shape form.
+    ///
+    /// Companions that are not wild shape forms are reported in the error popup.
+    pub fn toggle_wild_shape(&mut self, idx: usize) {
+        if !self.player.toggle_wild_shape(idx) {
+            self.show_error(String::from(
+                "only wild shape forms can be used for wild shape",
+            ));
+        }
+    }
+
+    /// Open the magic item menu, first tracking any magic items added to the inventory.
+    ///
+    /// Inventory lines naming a magic item lookup entry are tracked, and items that left
+    /// the inventory are dropped.
+    pub fn show_magic_items(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let mut found = vec![];
+        for line in self.player.inventory.iter() {
+            let Some(entry) = lookup.get_entry(line)? else {
+                continue;
+            };
+            let item = entry
+                .metadata
+                .as_ref()
+                .and_then(|m| MagicItem::from_metadata(line.trim(), m));
+            found.extend(item);
+        }
+        self.player.update_magic_items(found);
+
+        self.popup_scroll_provider.reset();
+        self.popup_scroll_provider
+            .set_max(self.player.magic_items.len() as u32);
+        self.selected = Some(Selected::MagicItems);
+        Ok(())
+    }
+
+    /// Get the lines of the magic item menu.
+    pub fn magic_item_lines(&self) -> Vec<String> {
+        self.player
+            .magic_items
+            .iter()
+            .map(|item| {
+                let mut line = item.name.clone();
+                if !item.rarity.is_empty() {
+                    line.push_str(&format!(" ({})", item.rarity));
+                }
+                if item.max_charges > 0 {
+                    line.push_str(&format!(", {}/{} charges", item.charges, item.max_charges));
+                }
+                if !item.recharge.is_empty() {
+                    line.push_str(&format!(", regains {}", item.recharge));
+                }
+                if item.attuned {
+                    line.push_str(" [attuned]");
+                } else if item.requires_attunement {
+                    line.push_str(" [not attuned]");
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Get the title of the magic item menu, with a warning if the player is attuned to
+    /// too many items.
+    pub fn magic_items_title(&self) -> String {
+        let attuned = self.player.attuned_count();
+        let warning = if attuned > MAX_ATTUNED {
+            " - too many!"
+        } else {
+            ""
+        };
+        format!(
+            "Magic Items (attuned {}/{}{})",
+            attuned, MAX_ATTUNED, warning
+        )
+    }
+
+    /// Attune to the item highlighted in the magic item menu, or end its attunement.
+    pub fn toggle_attunement(&mut self) {
+        let line = self.popup_scroll_provider.get_line() as usize;
+        let Some(item) = self.player.magic_items.get(line) else {
+            return;
+        };
+        if !item.requires_attunement && !item.attuned {
+            let error = format!("{} does not require attunement", item.name);
+            self.show_error(error);
+            return;
+        }
+        if let Some(warning) = self.player.toggle_attunement(line) {
+            self.show_error(warning);
+        }
+    }
+
+    /// Take a long rest and show everything the player regained.
+    pub fn long_rest(&mut self) {
+        let report = self.player.long_rest();
+        let text = if report.is_empty() {
+            String::from("Finished a long rest")
+        } else {
+            format!("Finished a long rest\n\n{}", report.join("\n"))
+        };
+        self.show_message("Long Rest", text);
+    }
+
+    /// Remove a companion, showing the player instead if it was being shown.
+    pub fn remove_companion(&mut self, idx: usize) {
+        self.player.remove_companion(idx);
+        match self.companion {
+            Some(current) if current == idx => self.view_companion(None),
+            Some(current) if current > idx => self.companion = Some(current - 1),
+            _ => {}
+        }
+    }
+
+    /// Recalculate the player's AC from their inventory.
+    ///
+    /// Armor the player is not proficient with is reported in the error popup.
+    pub fn recalculate_ac(&mut self) {
+        let (ac, warnings) = self.player.calculate_ac();
+        self.player.ac = ac;
+        if !warnings.is_empty() {
+            self.show_error(warnings.join("\n"));
+        }
+    }
+
+    /// Get a line describing the attack bonus of each weapon in the inventory.
+    pub fn attack_lines(&self) -> Vec<String> {
+        self.player
+            .inventory
+            .iter()
+            .filter_map(|line| {
+                let (bonus, proficient) = self.player.attack_bonus(line)?;
+                Some(format!(
+                    "{}: {:+} to hit{}",
+                    line.trim(),
+                    bonus,
+                    if proficient { "" } else { " (not proficient)" }
+                ))
+            })
+            .collect()
+    }
+
+    /// Show every rules-as-written problem with the player's skills.
+    pub fn show_skill_issues(&mut self) {
+        let issues = self.player.skill_issues();
+        let text = if issues.is_empty() {
+            String::from("No problems found")
+        } else {
+            issues
+                .into_iter()
+                .map(|(_, issue)| format!("- {}", issue))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        self.show_message("Skill Problems", text);
+    }
+
+    /// Show the player's class, race, spells and items that come from sources that are
+    /// not allowed in the error popup.
+    pub fn show_source_issues(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let allowed = &self.settings.allowed_sources;
+        let names = [
+            self.player.class.to_string(),
+            self.player.race.to_lookup_string().to_owned(),
+        ];
+        let lines = names
+            .iter()
+            .chain(self.player.spells.iter())
+            .chain(self.player.inventory.iter());
+
+        let mut issues = vec![];
+        for line in lines {
+            if let Some(entry) = lookup.get_entry(line)? {
+                if !entry.is_allowed(allowed) {
+                    issues.push(format!("- {} is from {}", entry.name, entry.source));
+                }
+            }
+        }
+
+        let text = if issues.is_empty() {
+            String::from("No problems found")
+        } else {
+            issues.join("\n")
+        };
+        self.show_error(text);
+        Ok(())
+    }
+
+    /// List every player file in the characters folder in the load menu.
+    ///
+    /// Each file is shown with the player's name, level and class. The folder is
+    /// created if it doesn't exist yet.
     pub fn lookup_files(&mut self) -> Result<()> {
-        let dir = std::env::current_dir()?;
-        let files = std::fs::read_dir(dir)?;
-
-        let names: Vec<String> = files
-            .map(|f| f.unwrap().path())
-            .filter(|f| {
-                f.is_file()
-                    && (f.extension().unwrap_or_default() == "player"
-                        || f.extension().unwrap_or_default() == "json"
-                        || f.extension().unwrap_or_default() == "yaml")
+        let dir = self.characters_dir()?;
+        let files = std::fs::read_dir(&dir)?;
+
+        let mut paths: Vec<PathBuf> = files
+            .filter_map(|f| f.ok().map(|f| f.path()))
+            .filter(|f| f.is_file() && is_player_file(f))
+            .collect();
+        paths.sort();
+        let files: Vec<(PathBuf, String)> = paths
+            .into_iter()
+            .map(|path| {
+                let preview = player_preview(&path);
+                (path, preview)
             })
-            .map(|f| f.to_str().unwrap_or(&f.to_string_lossy()).to_owned())
             .collect();
 
         self.selected = Some(Selected::Load);
-        self.popup_scroll_provider.set_max(names.len() as u32);
-        self.current_lookup = Some(LookupResult::Files(names));
+        self.popup_scroll_provider.set_max(files.len() as u32);
+        self.current_lookup = Some(LookupResult::Files(files));
         self.popup_scroll_provider.reset();
 
         Ok(())
     }
 
+    /// Get the folder players are kept in, creating it if it doesn't exist.
+    fn characters_dir(&self) -> Result<PathBuf> {
+        let dir = self.settings.characters_dir();
+        std::fs::create_dir_all(&dir).wrap_err_with(|| {
+            format!(
+                "failed to create characters folder '{}'",
+                dir.to_str().unwrap_or(&dir.to_string_lossy())
+            )
+        })?;
+        Ok(dir)
+    }
+
+    /// Copy a player file from elsewhere into the characters folder and load it.
+    pub fn import_player(&mut self, path: &str) -> Result<()> {
+        let path = PathBuf::from(path.trim());
+        let path = match path.strip_prefix("~") {
+            Ok(rest) => home::home_dir().unwrap_or_default().join(rest),
+            Err(_) => path,
+        };
+        if !is_player_file(&path) {
+            return Err(eyre!(
+                "'{}' is not a player file",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            ));
+        }
+        // Make sure the file is a valid player before copying it
+        Player::load(&path)?;
+
+        let target = self
+            .characters_dir()?
+            .join(path.file_name().unwrap_or_default());
+        if target.exists() {
+            return Err(eyre!(
+                "a player file named '{}' already exists in the characters folder",
+                target.file_name().unwrap_or_default().to_string_lossy()
+            ));
+        }
+        std::fs::copy(&path, &target).wrap_err_with(|| {
+            format!(
+                "failed to copy '{}' to the characters folder",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+
+        self.load_player(target)?;
+        self.tab_scroll_mut().reset();
+        self.update_tab(Tab::Notes);
+        self.current_lookup = None;
+        Ok(())
+    }
+
     /// Get all completions for the currently selected tab item
     pub fn complete_current_selection(&mut self, lookup: &mut Lookup) -> Result<()> {
         let item = self.tab_scroll_provider.get_line();
@@ -416,9 +1481,16 @@ impl App {
     }
 
     /// Get the completion result for the provided text
+    ///
+    /// Words such as `level:3` filter the results by their metadata, see `LookupEntry::matches()`.
+    /// Entries from sources that are not allowed are left out.
     pub fn get_completion(&mut self, text: &str, lookup: &mut Lookup) -> Result<LookupResult> {
+        self.search_terms.clear();
         let text = text.trim().to_ascii_lowercase();
-        let lookup = lookup.get_completions(&text)?;
+        let (name, filters) = Lookup::filters(&text);
+        let mut lookup = lookup.get_completions(&name)?;
+        let allowed = &self.settings.allowed_sources;
+        lookup.retain(|e| e.matches(&filters) && e.is_allowed(allowed));
 
         // Probably shouldn't clone but the lifetimes were too confusing :(
         Ok(if !lookup.is_empty() {
@@ -430,6 +1502,72 @@ impl App {
         })
     }
 
+    /// Search the descriptions of all lookup entries for the provided text.
+    ///
+    /// The searched words are kept so they can be highlighted in the chosen entry. Filters
+    /// such as `level:3` and allowed sources work as in `App::get_completion()`.
+    pub fn search_descriptions(&mut self, text: &str, lookup: &mut Lookup) -> Result<LookupResult> {
+        let (search, filters) = Lookup::filters(text);
+        let mut results = if search.is_empty() {
+            lookup.get_completions("")?
+        } else {
+            lookup.search_descriptions(&search)?
+        };
+        let allowed = &self.settings.allowed_sources;
+        results.retain(|e| e.matches(&filters) && e.is_allowed(allowed));
+        self.search_terms = Lookup::terms(&search);
+
+        Ok(if !results.is_empty() {
+            self.popup_scroll_provider.set_max(results.len() as u32);
+            LookupResult::Completion(results)
+        } else {
+            self.popup_scroll_provider.set_max(1);
+            LookupResult::Invalid(text.trim().to_owned())
+        })
+    }
+
+    /// Get the results for the text in the free lookup buffer.
+    ///
+    /// Text starting with `/` searches entry descriptions instead of names.
+    /// Results are limited to the current lookup category, if any.
+    pub fn free_lookup(&mut self, lookup: &mut Lookup) -> Result<LookupResult> {
+        let text = self.lookup_buffer.clone();
+        let mut result = match text.strip_prefix('/') {
+            Some(search) => self.search_descriptions(search, lookup)?,
+            None => self.get_completion(&text, lookup)?,
+        };
+
+        if let (Some(category), LookupResult::Completion(entries)) =
+            (&self.lookup_category, &mut result)
+        {
+            entries.retain(|e| &e.category == category);
+            if entries.is_empty() {
+                self.popup_scroll_provider.set_max(1);
+                result = LookupResult::Invalid(format!("{} (in {})", text.trim(), category));
+            } else {
+                self.popup_scroll_provider.set_max(entries.len() as u32);
+            }
+        }
+
+        self.popup_scroll_provider.reset();
+        Ok(result)
+    }
+
+    /// Limit free lookup results to the next category, or to none after the last one.
+    pub fn cycle_lookup_category(&mut self, lookup: &mut Lookup) -> Result<()> {
+        let categories = lookup.categories()?;
+        let next = match &self.lookup_category {
+            None => 0,
+            Some(current) => categories
+                .iter()
+                .position(|c| c == current)
+                .map_or(0, |i| i + 1),
+        };
+        self.lookup_category = categories.get(next).cloned();
+        self.current_lookup = Some(self.free_lookup(lookup)?);
+        Ok(())
+    }
+
     /// Complete the current text using the current selection
     pub fn finish_completion(&mut self) {
         let comp_item = self.popup_scroll_provider.get_line();
@@ -447,12 +1585,15 @@ impl App {
             _ => panic!("attempt to finish completion while not in completion mode"),
         };
 
+        // Completions may not start with the typed text, so replace it while keeping indentation
         let current = &mut self.current_tab_mut()[tab_item as usize];
-        current.push_str(&completion[current.trim().len()..]);
+        let indent = current.len() - current.trim_start().len();
+        current.truncate(indent);
+        current.push_str(&completion);
     }
 
     /// Get the control type associated with the currently selected item.
-    pub fn get_selected_type(&mut self) -> Option<ControlType> {
+    pub fn get_selected_type(&mut self) -> Option<ControlType<'_>> {
         match self.selected {
             None
             | Some(
@@ -464,24 +1605,54 @@ impl App {
                 | Selected::Funds
                 | Selected::FreeLookupSelect
                 | Selected::Proficiency
+                | Selected::ClassSkills
+                | Selected::Attacks
+                | Selected::Suggestions
+                | Selected::Companions
+                | Selected::MagicItems
+                | Selected::FileMenu
+                | Selected::SaveAs
+                | Selected::Rename
+                | Selected::Import
+                | Selected::UnsavedChanges
+                | Selected::HomebrewDiscard
+                | Selected::Backups
                 | Selected::Error
+                | Selected::History
                 | Selected::Load,
             ) => None,
+            Some(Selected::Homebrew) => match self.popup_scroll_provider.get_line() {
+                0 => Some(ControlType::TextInput(&mut self.homebrew_entry.name)),
+                1 => Some(ControlType::TextInput(&mut self.homebrew_entry.category)),
+                2 => Some(ControlType::TextInput(
+                    &mut self.homebrew_entry.description_short,
+                )),
+                4 => Some(ControlType::TextInput(&mut self.homebrew_entry.description)),
+                _ => None,
+            },
             Some(Selected::TopBarItem) => match self.index {
                 0 => Some(ControlType::TextInput(&mut self.player.name)),
                 1 => Some(ControlType::CycleFn(
-                    |app| app.player.update_race(app.player.race.get_prev()),
-                    |app| app.player.update_race(app.player.race.get_next()),
+                    |app| {
+                        app.player
+                            .update_race(app.player.race.get_prev(app.player.ruleset))
+                    },
+                    |app| {
+                        app.player
+                            .update_race(app.player.race.get_next(app.player.ruleset))
+                    },
                 )),
                 2 => Some(ControlType::CycleRecalc(&mut self.player.level, 1, 20)),
                 3 => Some(ControlType::CycleFn(
                     |app| {
-                        app.player.class.cycle_prev();
-                        app.player.recalculate();
+                        let mut class = app.player.class;
+                        class.cycle_prev();
+                        app.player.update_class(class);
                     },
                     |app| {
-                        app.player.class.cycle_next();
-                        app.player.recalculate();
+                        let mut class = app.player.class;
+                        class.cycle_next();
+                        app.player.update_class(class);
                     },
                 )),
                 4 => Some(ControlType::CycleFn(
@@ -496,13 +1667,55 @@ impl App {
                         app.player.recalculate()
                     },
                 )),
+                5 => Some(ControlType::CycleFn(
+                    |app| {
+                        let mut ruleset = app.player.ruleset;
+                        ruleset.cycle_prev();
+                        app.player.update_ruleset(ruleset);
+                    },
+                    |app| {
+                        let mut ruleset = app.player.ruleset;
+                        ruleset.cycle_next();
+                        app.player.update_ruleset(ruleset);
+                    },
+                )),
                 _ => unreachable!(),
             },
-            Some(Selected::StatItem) => Some(ControlType::CycleRecalc(
-                &mut self.player.stats[self.index as usize],
-                1,
-                30,
-            )),
+            Some(Selected::StatItem) => match self.companion {
+                Some(idx) => Some(ControlType::Cycle(
+                    &mut self.player.companions[idx].stats[self.index as usize],
+                    1,
+                    30,
+                )),
+                None => Some(ControlType::CycleRecalc(
+                    &mut self.player.stats[self.index as usize],
+                    1,
+                    30,
+                )),
+            },
+            Some(Selected::InfoItem) if self.companion.is_some() => {
+                let companion = &mut self.player.companions[self.companion.unwrap()];
+                match self.index {
+                    0 => Some(ControlType::TextInput(&mut companion.name)),
+                    1 => Some(ControlType::Cycle(&mut companion.hp, 0, companion.max_hp)),
+                    2 => Some(ControlType::Cycle(&mut companion.max_hp, 1, u32::MAX)),
+                    3 => Some(ControlType::Cycle(&mut companion.temp_hp, 0, u32::MAX)),
+                    4 => Some(ControlType::Cycle(&mut companion.ac, 0, 50)),
+                    5 => Some(ControlType::CycleFn(
+                        |app| {
+                            app.player.companions[app.companion.unwrap()]
+                                .kind
+                                .cycle_prev()
+                        },
+                        |app| {
+                            app.player.companions[app.companion.unwrap()]
+                                .kind
+                                .cycle_next()
+                        },
+                    )),
+                    _ => unreachable!(),
+                }
+            }
             Some(Selected::InfoItem) => match self.index {
                 0 => Some(ControlType::Cycle(
                     &mut self.player.hp,
@@ -512,12 +1725,12 @@ impl App {
                 1 => Some(ControlType::Cycle(
                     &mut self.player.max_hp,
                     1,
-                    std::u32::MAX,
+                    u32::MAX,
                 )),
                 2 => Some(ControlType::Cycle(
                     &mut self.player.temp_hp,
                     0,
-                    std::u32::MAX,
+                    u32::MAX,
                 )),
                 3 => Some(ControlType::Cycle(&mut self.player.ac, 0, 50)),
                 4 => Some(ControlType::Cycle(&mut self.player.prof_bonus, 2, 6)),
@@ -527,21 +1740,74 @@ impl App {
                     self.player.hit_dice,
                 )),
                 6 => Some(ControlType::CycleFn(
-                    |app| app.player.background.cycle_prev(),
-                    |app| app.player.background.cycle_next(),
+                    |app| {
+                        app.player.update_background(
+                            app.player.background.get_prev(app.player.ruleset),
+                        )
+                    },
+                    |app| {
+                        app.player.update_background(
+                            app.player.background.get_next(app.player.ruleset),
+                        )
+                    },
                 )),
+                7 => Some(ControlType::Cycle(&mut self.player.exhaustion, 0, 6)),
                 _ => unreachable!(),
             },
-            Some(Selected::TabItem) => Some(ControlType::TextInput(match self.current_tab {
-                Tab::Notes => &mut self.player.notes[self.tab_scroll_provider.get_line() as usize],
-                Tab::Inventory => {
-                    &mut self.player.inventory[self.tab_scroll_provider.get_line() as usize]
-                }
-                Tab::Spells => {
-                    &mut self.player.spells[self.tab_scroll_provider.get_line() as usize]
-                }
-            })),
+            Some(Selected::TabItem) => {
+                let line = self.tab_scroll_provider.get_line() as usize;
+                Some(ControlType::TextInput(&mut self.current_tab_mut()[line]))
+            }
             Some(Selected::FreeLookup) => Some(ControlType::TextInput(&mut self.lookup_buffer)),
+            Some(Selected::Training) => {
+                let line = self.popup_scroll_provider.get_line() as usize;
+                self.player
+                    .proficiencies
+                    .nth_mut(self.index)
+                    .get_mut(line)
+                    .map(ControlType::TextInput)
+            }
+        }
+    }
+}
+
+/// Whether a file has the extension of a player file.
+fn is_player_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("player" | "json" | "yaml" | "yml")
+    )
+}
+
+/// Turn a player's name into a file name, without the extension.
+///
+/// Characters that cannot be used in file names are replaced, and leading or trailing
+/// dots and spaces are removed so the name cannot be `..` or a hidden file. Returns
+/// `None` if nothing is left.
+fn file_stem(name: &str) -> Option<String> {
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    (!stem.is_empty()).then(|| stem.to_owned())
+}
+
+/// Describe the player in a file for the load menu, as in `Tordek, level 3 Fighter (tordek.yaml)`.
+fn player_preview(path: &Path) -> String {
+    let file = path.file_name().unwrap_or_default().to_string_lossy();
+    match Player::load(path) {
+        Ok(player) if player.name.is_empty() => {
+            format!("level {} {} ({})", player.level, player.class, file)
         }
+        Ok(player) => format!(
+            "{}, level {} {} ({})",
+            player.name, player.level, player.class, file
+        ),
+        Err(_) => format!("{} (could not be read)", file),
     }
 }
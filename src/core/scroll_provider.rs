@@ -2,6 +2,8 @@
 pub struct ScrollProvider {
     current_scroll: u32,
     frame_height: u32,
+    /// The width text in the frame wraps at, or zero if it does not wrap.
+    frame_width: u16,
     current_line: u32,
     maximum_lines: Option<u32>,
 }
@@ -13,6 +15,7 @@ impl ScrollProvider {
             current_scroll: 0,
             current_line: 0,
             frame_height,
+            frame_width: 0,
             maximum_lines,
         }
     }
@@ -56,6 +59,22 @@ impl ScrollProvider {
         self.scroll_up(amount);
     }
 
+    /// Scroll so the given line is at the top of the frame.
+    pub fn scroll_to(&mut self, line: u32) {
+        self.current_scroll = line;
+        self.current_line = line;
+    }
+
+    /// Scroll just far enough that the given line is inside the frame.
+    pub fn show_line(&mut self, line: u32) {
+        if line < self.current_scroll {
+            self.current_scroll = line;
+        } else if line >= self.current_scroll + self.frame_height {
+            self.current_scroll = (line + 1).saturating_sub(self.frame_height);
+        }
+        self.current_line = line;
+    }
+
     pub fn set_max(&mut self, mut max: u32) {
         max = max.max(1);
         self.maximum_lines = Some(max);
@@ -74,6 +93,15 @@ impl ScrollProvider {
         self.frame_height = height
     }
 
+    pub fn update_frame_width(&mut self, width: u16) {
+        self.frame_width = width
+    }
+
+    /// Get the width text in the frame wraps at, or zero if it does not wrap.
+    pub fn get_frame_width(&self) -> u16 {
+        self.frame_width
+    }
+
     pub fn get_scroll(&self) -> u32 {
         self.current_scroll
     }
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use color_eyre::eyre::{Context, Report, Result};
 use ratatui::style::Color;
@@ -67,6 +67,18 @@ pub struct Settings {
     pub popup_foreground: TermColor,
     pub highlight: TermColor,
     pub tab_select: TermColor,
+    /// The sources lookup entries may come from, by name or abbreviation.
+    ///
+    /// Every source is allowed if this is empty.
+    pub allowed_sources: Vec<String>,
+    /// The folder players are kept in, listed by the load menu.
+    ///
+    /// A leading `~` stands for the user's home directory.
+    pub characters_dir: PathBuf,
+    /// The number of backups kept of each player file, made each time it is saved.
+    ///
+    /// No backups are made if this is zero.
+    pub backups: usize,
 }
 
 impl Settings {
@@ -77,7 +89,7 @@ impl Settings {
         }
     }
 
-    fn load(path: &Path) -> Result<Self> {
+    pub fn load(path: &Path) -> Result<Self> {
         let data = std::fs::read(path).wrap_err_with(|| {
             format!(
                 "failed to load settings from file '{}'",
@@ -92,6 +104,14 @@ impl Settings {
         })?;
         Ok(settings)
     }
+
+    /// Get the folder players are kept in, with any leading `~` expanded.
+    pub fn characters_dir(&self) -> PathBuf {
+        match self.characters_dir.strip_prefix("~") {
+            Ok(rest) => home::home_dir().unwrap_or_default().join(rest),
+            Err(_) => self.characters_dir.clone(),
+        }
+    }
 }
 
 impl Default for Settings {
@@ -104,6 +124,9 @@ impl Default for Settings {
             popup_foreground: TermColor::Black,
             highlight: TermColor::Green,
             tab_select: TermColor::LightGray,
+            allowed_sources: vec![],
+            characters_dir: PathBuf::from("~/.scrim/characters"),
+            backups: 5,
         }
     }
 }
@@ -1,11 +1,13 @@
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
+use notify::{event::ModifyKind, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
+    path::Path,
     sync::mpsc,
     thread,
     time::{Duration, Instant},
 };
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, WrapErr};
 
 #[derive(Clone, Copy, Debug)]
 pub enum Event {
@@ -13,6 +15,10 @@ pub enum Event {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Resize(u16, u16),
+    /// A lookup file was added, changed or removed.
+    LookupsChanged,
+    /// The settings file was changed.
+    SettingsChanged,
 }
 
 #[derive(Debug)]
@@ -22,6 +28,8 @@ pub struct EventHandler {
     reciever: mpsc::Receiver<Event>,
     #[allow(dead_code)]
     handler: thread::JoinHandle<()>,
+    /// The file watchers started by `EventHandler::watch()`, which stop when dropped.
+    watchers: Vec<RecommendedWatcher>,
 }
 
 impl EventHandler {
@@ -65,9 +73,61 @@ impl EventHandler {
             sender,
             reciever,
             handler,
+            watchers: vec![],
         }
     }
 
+    /// Send `event` whenever a file in the given directory and its subdirectories changes,
+    /// if `filter` accepts the path of the file.
+    ///
+    /// Editors often write a file several times when saving, so changes are collected
+    /// for a short while and sent as a single event.
+    pub fn watch<F>(&mut self, dir: &Path, filter: F, event: Event) -> Result<()>
+    where
+        F: Fn(&Path) -> bool + Send + 'static,
+    {
+        const DEBOUNCE: Duration = Duration::from_millis(200);
+
+        let (changed, changes) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                let Ok(e) = res else {
+                    return;
+                };
+                // Reading a file can touch its metadata, which must not trigger a reload
+                let changes_content =
+                    matches!(
+                        e.kind,
+                        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+                    ) && !matches!(e.kind, EventKind::Modify(ModifyKind::Metadata(_)));
+                if changes_content && e.paths.iter().any(|p| filter(p)) {
+                    let _ = changed.send(());
+                }
+            })?;
+        watcher
+            .watch(dir, RecursiveMode::Recursive)
+            .wrap_err_with(|| {
+                format!(
+                    "failed to watch '{}' for changes",
+                    dir.to_str().unwrap_or(&dir.to_string_lossy())
+                )
+            })?;
+
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            while changes.recv().is_ok() {
+                thread::sleep(DEBOUNCE);
+                while changes.try_recv().is_ok() {}
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.watchers.push(watcher);
+        Ok(())
+    }
+
     /// Recieve the next event from the handler thread.
     ///
     /// This function will always block the current thread if
@@ -0,0 +1,1602 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    path::Path,
+};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde_derive::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{core::backups::write_atomic, lookup::file_category};
+
+/// The top level keys of 5etools data files that hold importable entries.
+const FIVE_E_TOOLS_KEYS: [&str; 14] = [
+    "spell",
+    "item",
+    "baseitem",
+    "feat",
+    "optionalfeature",
+    "race",
+    "class",
+    "subclass",
+    "background",
+    "condition",
+    "disease",
+    "status",
+    "action",
+    "monster",
+];
+
+/// The categories with typed metadata, see `Metadata`.
+const METADATA_KINDS: [&str; 8] = [
+    "spell", "weapon", "armor", "item", "feat", "race", "class", "monster",
+];
+
+/// The 5etools tags that refer to something that may have its own lookup entry.
+const LINK_TAGS: [&str; 12] = [
+    "spell",
+    "item",
+    "condition",
+    "disease",
+    "status",
+    "creature",
+    "feat",
+    "race",
+    "class",
+    "background",
+    "action",
+    "skill",
+];
+
+/// A lookup entry converted from another tool's data.
+///
+/// Structured fields such as spell level or item weight are kept next to the usual
+/// entry fields, and are also listed at the top of the description.
+#[derive(Serialize)]
+struct ImportedEntry {
+    name: String,
+    description_short: String,
+    description: String,
+    category: String,
+    #[serde(flatten)]
+    fields: BTreeMap<String, Value>,
+    /// Typed metadata fields, in a section named after the kind of entry.
+    #[serde(flatten)]
+    metadata: BTreeMap<String, BTreeMap<String, Value>>,
+    /// `**Label:** value` lines shown above the body of the description.
+    #[serde(skip)]
+    labels: Vec<String>,
+}
+
+impl ImportedEntry {
+    fn new(name: &str, category: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            description_short: String::new(),
+            description: String::new(),
+            category: category.to_owned(),
+            fields: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+            labels: vec![],
+        }
+    }
+
+    /// Keep a structured field, skipping empty values.
+    ///
+    /// Fields of entries with typed metadata, other than their source, go in the metadata
+    /// section.
+    fn field(&mut self, key: &str, value: impl Into<Value>) {
+        let value = value.into();
+        let empty = match &value {
+            Value::Null => true,
+            Value::String(s) => s.is_empty(),
+            Value::Array(a) => a.is_empty(),
+            _ => false,
+        };
+        if empty {
+            return;
+        }
+
+        // Magic items share the metadata of other items
+        let kind = match self.category.as_str() {
+            "magic item" => "item",
+            category => category,
+        };
+        if METADATA_KINDS.contains(&kind) && key != "source" {
+            self.metadata
+                .entry(kind.to_owned())
+                .or_default()
+                .insert(key.to_owned(), value);
+        } else {
+            self.fields.insert(key.to_owned(), value);
+        }
+    }
+
+    /// Keep a comma separated list of saving throws.
+    fn saving_throws(&mut self, saves: String) {
+        if !saves.is_empty() {
+            self.labels.push(format!("**Saving Throws:** {}", saves));
+        }
+        let saves: Vec<Value> = saves.split(", ").map(Value::from).collect();
+        self.field("saving_throws", saves);
+    }
+
+    /// Keep a structured text field and show it at the top of the description.
+    fn labelled(&mut self, key: &str, label: &str, value: String) {
+        if !value.is_empty() {
+            self.labels.push(format!("**{}:** {}", label, value));
+        }
+        self.field(key, value);
+    }
+
+    /// Set the body of the description, below any labelled fields.
+    fn body(mut self, body: String) -> Self {
+        self.description = match (self.labels.is_empty(), body.is_empty()) {
+            (true, _) => body,
+            (false, true) => self.labels.join("\n"),
+            (false, false) => format!("{}\n\n{}", self.labels.join("\n"), body),
+        };
+        self
+    }
+}
+
+/// Imported entries keyed by their lowercase name, as in a lookup file.
+#[derive(Serialize, Default)]
+struct ImportedLookup {
+    entries: BTreeMap<String, ImportedEntry>,
+    /// Entries that were not imported, and why.
+    #[serde(skip)]
+    skipped: Vec<String>,
+}
+
+impl ImportedLookup {
+    /// Add an entry, telling apart entries with the same name by their source.
+    ///
+    /// Entries with the same name and source as an earlier one are skipped.
+    fn add(&mut self, mut entry: ImportedEntry) {
+        let source = |entry: &ImportedEntry| entry.fields.get("source").map(text);
+        let first = match self.entries.entry(entry.name.to_lowercase()) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+                return;
+            }
+            Entry::Occupied(first) => source(first.get()),
+        };
+
+        let name = match source(&entry).filter(|s| !s.is_empty()) {
+            Some(source) => format!("{} ({})", entry.name, source),
+            None => format!("{} (2)", entry.name),
+        };
+        let key = name.to_lowercase();
+        if first == source(&entry) || self.entries.contains_key(&key) {
+            let from = match source(&entry).filter(|s| !s.is_empty()) {
+                Some(source) => format!(" from {}", source),
+                None => String::new(),
+            };
+            self.skipped.push(format!(
+                "skipped '{}'{} because an entry with the same name and source was already imported",
+                entry.name, from
+            ));
+            return;
+        }
+        entry.name = name;
+        self.entries.insert(key, entry);
+    }
+}
+
+/// Convert a 5etools or Open5e/SRD JSON file into a YAML lookup file.
+///
+/// 5etools files are recognised by their top level keys, such as `spell` or `item`.
+/// Anything else is read as a list of Open5e or SRD API results, either at the top
+/// level or in a `results` field. An existing output file is only replaced if `force`
+/// is set. Returns the number of imported entries, and why each skipped entry was skipped.
+pub fn import(input: &Path, output: &Path, force: bool) -> Result<(usize, Vec<String>)> {
+    if output.exists() && !force {
+        return Err(eyre!(
+            "'{}' already exists, pass --force to replace it",
+            output.to_string_lossy()
+        ));
+    }
+    let text = std::fs::read_to_string(input)
+        .wrap_err_with(|| format!("failed to read '{}'", input.to_string_lossy()))?;
+    let json: Value = serde_json::from_str(&text)
+        .wrap_err_with(|| format!("failed to parse '{}'", input.to_string_lossy()))?;
+
+    let mut lookup = ImportedLookup::default();
+    match &json {
+        Value::Object(data) if FIVE_E_TOOLS_KEYS.iter().any(|k| data.contains_key(*k)) => {
+            import_5etools(data, &mut lookup)
+        }
+        Value::Object(data) => {
+            if let Some(Value::Array(results)) = data.get("results") {
+                import_open5e(results, &file_category(input), &mut lookup);
+            }
+        }
+        Value::Array(results) => import_open5e(results, &file_category(input), &mut lookup),
+        _ => {}
+    }
+
+    if lookup.entries.is_empty() {
+        return Err(eyre!(
+            "no 5etools or Open5e entries found in '{}'",
+            input.to_string_lossy()
+        ));
+    }
+
+    let yaml = serde_yaml::to_string(&lookup)?;
+    write_atomic(output, &yaml)?;
+    Ok((lookup.entries.len(), lookup.skipped))
+}
+
+/// Import every known kind of entry from a 5etools data file.
+fn import_5etools(data: &Map<String, Value>, lookup: &mut ImportedLookup) {
+    let list = |key: &str| -> Vec<&Value> {
+        match data.get(key) {
+            Some(Value::Array(values)) => values.iter().collect(),
+            _ => vec![],
+        }
+    };
+
+    for spell in list("spell") {
+        lookup.add(spell_5etools(spell));
+    }
+    for item in list("baseitem").into_iter().chain(list("item")) {
+        lookup.add(item_5etools(item));
+    }
+    for race in list("race") {
+        lookup.add(race_5etools(race));
+    }
+    for class in list("class") {
+        lookup.add(class_5etools(class, &list("classFeature")));
+    }
+    for subclass in list("subclass") {
+        lookup.add(subclass_5etools(subclass, &list("subclassFeature")));
+    }
+    for monster in list("monster") {
+        lookup.add(monster_5etools(monster));
+    }
+
+    let generic = [
+        ("feat", "feat"),
+        ("optionalfeature", "feat"),
+        ("background", "background"),
+        ("condition", "condition"),
+        ("disease", "condition"),
+        ("status", "condition"),
+        ("action", "action"),
+    ];
+    for (key, category) in generic {
+        for value in list(key) {
+            let mut entry = ImportedEntry::new(&text(&value["name"]), category);
+            entry.field("source", text(&value["source"]));
+            if let Some(prerequisite) = value.get("prerequisite") {
+                entry.labelled(
+                    "prerequisite",
+                    "Prerequisite",
+                    prerequisite_5etools(prerequisite),
+                );
+            }
+            lookup.add(entry.body(entries_5etools(&value["entries"])));
+        }
+    }
+}
+
+/// Convert a 5etools spell.
+fn spell_5etools(spell: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&spell["name"]), "spell");
+    let level = spell["level"].as_u64().unwrap_or_default();
+    let school = match text(&spell["school"]).as_str() {
+        "A" => "Abjuration",
+        "C" => "Conjuration",
+        "D" => "Divination",
+        "E" => "Enchantment",
+        "V" => "Evocation",
+        "I" => "Illusion",
+        "N" => "Necromancy",
+        "T" => "Transmutation",
+        "P" => "Psionic",
+        _ => "",
+    };
+    let ritual = spell["meta"]["ritual"].as_bool().unwrap_or_default();
+    let concentration = spell["duration"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|d| d["concentration"].as_bool().unwrap_or_default());
+
+    entry.description_short = spell_level(level, school, ritual);
+    entry.field("level", level);
+    entry.field("school", school);
+    entry.labelled(
+        "casting_time",
+        "Casting Time",
+        join(&spell["time"], |t| {
+            let unit = text(&t["unit"]).replace("bonus", "bonus action");
+            let mut time = count(t["number"].as_u64().unwrap_or(1), &unit);
+            if let Some(condition) = t["condition"].as_str() {
+                time = format!("{}, {}", time, strip_tags(condition));
+            }
+            time
+        }),
+    );
+    entry.labelled("range", "Range", range_5etools(&spell["range"]));
+    entry.labelled(
+        "components",
+        "Components",
+        components(
+            spell["components"]["v"].as_bool().unwrap_or_default(),
+            spell["components"]["s"].as_bool().unwrap_or_default(),
+            match &spell["components"]["m"] {
+                Value::Object(m) => text(&m["text"]),
+                Value::String(m) => m.clone(),
+                Value::Bool(true) => String::from(" "),
+                _ => String::new(),
+            },
+        ),
+    );
+    entry.labelled(
+        "duration",
+        "Duration",
+        join(&spell["duration"], |d| match text(&d["type"]).as_str() {
+            "instant" => String::from("Instantaneous"),
+            "timed" => {
+                let amount = d["duration"]["amount"].as_u64().unwrap_or(1);
+                let time = count(amount, &text(&d["duration"]["type"]));
+                if d["concentration"].as_bool().unwrap_or_default() {
+                    format!("Concentration, up to {}", time)
+                } else {
+                    time
+                }
+            }
+            "permanent" => String::from("Until dispelled"),
+            _ => String::from("Special"),
+        }),
+    );
+    entry.field("ritual", ritual);
+    entry.field("concentration", concentration);
+    let classes: Vec<String> = spell["classes"]["fromClassList"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|c| text(&c["name"]))
+        .collect();
+    entry.field("classes", classes.clone());
+    entry.field("source", text(&spell["source"]));
+
+    let mut body = entries_5etools(&spell["entries"]);
+    let higher = entries_5etools(&spell["entriesHigherLevel"]);
+    if !higher.is_empty() {
+        body = format!(
+            "{}
+
+{}",
+            body, higher
+        );
+    }
+    if !classes.is_empty() {
+        body = format!(
+            "{}
+
+**Spell Lists.** {}",
+            body,
+            classes.join(", ")
+        );
+    }
+    entry.body(body)
+}
+
+/// Convert a 5etools item or base item, such as a weapon or piece of armor.
+fn item_5etools(item: &Value) -> ImportedEntry {
+    // Newer data files add the source to the type, as in `M|XPHB`
+    let kind = text(&item["type"]);
+    let kind = kind.split('|').next().unwrap_or_default();
+    let rarity = text(&item["rarity"]);
+    let magic = !rarity.is_empty() && rarity != "none" && rarity != "unknown";
+    let category = match kind {
+        "M" | "R" => "weapon",
+        "LA" | "MA" | "HA" | "S" => "armor",
+        _ if item.get("weaponCategory").is_some() => "weapon",
+        _ if magic => "magic item",
+        _ => "item",
+    };
+    let mut entry = ImportedEntry::new(&text(&item["name"]), category);
+
+    let kind_name = match kind {
+        "M" => format!(
+            "{} melee weapon",
+            capitalize(&text(&item["weaponCategory"]))
+        ),
+        "R" => format!(
+            "{} ranged weapon",
+            capitalize(&text(&item["weaponCategory"]))
+        ),
+        "A" | "AF" => String::from("Ammunition"),
+        "LA" => String::from("Light armor"),
+        "MA" => String::from("Medium armor"),
+        "HA" => String::from("Heavy armor"),
+        "S" => String::from("Shield"),
+        "P" => String::from("Potion"),
+        "RG" => String::from("Ring"),
+        "RD" => String::from("Rod"),
+        "WD" => String::from("Wand"),
+        "ST" => String::from("Staff"),
+        "SC" => String::from("Scroll"),
+        "AT" => String::from("Artisan's tools"),
+        "T" => String::from("Tools"),
+        "INS" => String::from("Musical instrument"),
+        "GS" => String::from("Gaming set"),
+        _ if item["wondrous"].as_bool().unwrap_or_default() => String::from("Wondrous item"),
+        _ => String::from("Adventuring gear"),
+    };
+    let attunement = match &item["reqAttune"] {
+        Value::Bool(true) => String::from("requires attunement"),
+        Value::String(by) => format!("requires attunement {}", by),
+        _ => String::new(),
+    };
+
+    entry.description_short = kind_name.trim().to_owned();
+    if magic {
+        entry.description_short = format!("{}, {}", entry.description_short, rarity);
+        entry.field("rarity", rarity);
+    }
+    if !attunement.is_empty() {
+        entry.description_short = format!("{} ({})", entry.description_short, attunement);
+        entry.field("attunement", attunement);
+    }
+    if let Ok(charges) = text(&item["charges"]).parse::<u64>() {
+        entry.field("charges", charges);
+        entry.labels.push(format!("**Charges:** {}", charges));
+        entry.labelled("recharge", "Recharge", recharge_5etools(item));
+    }
+
+    entry.labelled(
+        "cost",
+        "Cost",
+        item["value"].as_f64().map(cost).unwrap_or_default(),
+    );
+    entry.labelled(
+        "weight",
+        "Weight",
+        item["weight"]
+            .as_f64()
+            .map(|w| format!("{} lb.", w))
+            .unwrap_or_default(),
+    );
+
+    if category == "weapon" {
+        entry.field("weapon_category", text(&item["weaponCategory"]));
+        let damage_type = match text(&item["dmgType"]).as_str() {
+            "B" => "bludgeoning",
+            "P" => "piercing",
+            "S" => "slashing",
+            "A" => "acid",
+            "C" => "cold",
+            "F" => "fire",
+            "O" => "force",
+            "L" => "lightning",
+            "N" => "necrotic",
+            "I" => "poison",
+            "Y" => "psychic",
+            "R" => "radiant",
+            "T" => "thunder",
+            _ => "",
+        };
+        let damage = format!("{} {}", text(&item["dmg1"]), damage_type);
+        entry.labelled("damage", "Damage", damage.trim().to_owned());
+
+        let properties = item["property"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|p| {
+                // Newer data files wrap properties in objects and add their source
+                let code = match p {
+                    Value::Object(p) => text(&p["uid"]),
+                    p => text(p),
+                };
+                let code = code.split('|').next().unwrap_or_default().to_owned();
+                match code.as_str() {
+                    "A" | "AF" => format!("ammunition ({})", text(&item["range"])),
+                    "T" => format!("thrown ({})", text(&item["range"])),
+                    "V" => format!("versatile ({})", text(&item["dmg2"])),
+                    "F" => String::from("finesse"),
+                    "H" => String::from("heavy"),
+                    "L" => String::from("light"),
+                    "LD" => String::from("loading"),
+                    "R" => String::from("reach"),
+                    "RLD" => String::from("reload"),
+                    "S" => String::from("special"),
+                    "2H" => String::from("two-handed"),
+                    "BF" => String::from("burst fire"),
+                    _ => code,
+                }
+            })
+            .map(Value::from)
+            .collect::<Vec<_>>();
+        if !properties.is_empty() {
+            entry.labels.push(format!(
+                "**Properties:** {}",
+                properties.iter().map(text).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        entry.field("properties", properties);
+    }
+
+    if category == "armor" {
+        let ac = item["ac"].as_u64().unwrap_or_default();
+        entry.field("armor_category", entry.description_short.clone());
+        entry.field("ac", ac);
+        entry.labels.push(format!(
+            "**Armor Class:** {}",
+            match kind {
+                "LA" => format!("{} + Dex modifier", ac),
+                "MA" => format!("{} + Dex modifier (max 2)", ac),
+                "S" => format!("+{}", ac),
+                _ => ac.to_string(),
+            }
+        ));
+        if let Ok(strength) = text(&item["strength"]).parse::<u64>() {
+            entry.field("strength", strength);
+            entry.labels.push(format!("**Strength:** {}", strength));
+        }
+        if item["stealth"].as_bool().unwrap_or_default() {
+            entry.field("stealth_disadvantage", true);
+            entry.labels.push(String::from("**Stealth:** Disadvantage"));
+        }
+    }
+
+    entry.field("source", text(&item["source"]));
+    entry.body(entries_5etools(&item["entries"]))
+}
+
+/// Describe how many charges a 5etools item regains and when, as in `1d6 + 1 at dawn`.
+fn recharge_5etools(item: &Value) -> String {
+    let when = match text(&item["recharge"]).as_str() {
+        "dawn" => "at dawn",
+        "dusk" => "at dusk",
+        "midnight" => "at midnight",
+        "restShort" => "on a short rest",
+        "restLong" => "on a long rest",
+        _ => return String::new(),
+    };
+    let amount = match &item["rechargeAmount"] {
+        Value::Null => String::from("all"),
+        amount => strip_tags(&text(amount)),
+    };
+    format!("{} {}", amount, when)
+}
+
+/// Convert a 5etools race or species.
+fn race_5etools(race: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&race["name"]), "race");
+    let size = join(&race["size"], |s| size(&text(s)).to_owned());
+    let speed = match &race["speed"] {
+        Value::Object(speed) => text(&speed["walk"]),
+        speed => text(speed),
+    };
+    let ability = join(&race["ability"], |a| {
+        let Value::Object(bonuses) = a else {
+            return String::new();
+        };
+        bonuses
+            .iter()
+            .filter_map(|(stat, bonus)| Some(format!("{} {:+}", ability(stat)?, bonus.as_i64()?)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+
+    entry.labelled("size", "Size", size);
+    entry.labelled(
+        "speed",
+        "Speed",
+        if speed.is_empty() {
+            speed
+        } else {
+            format!("{} feet", speed)
+        },
+    );
+    entry.labelled("ability", "Ability Scores", ability);
+    entry.field("source", text(&race["source"]));
+    entry.body(entries_5etools(&race["entries"]))
+}
+
+/// Convert a 5etools class, with the description made up of its features by level.
+fn class_5etools(class: &Value, features: &[&Value]) -> ImportedEntry {
+    let name = text(&class["name"]);
+    let mut entry = ImportedEntry::new(&name, "class");
+    if let Some(faces) = class["hd"]["faces"].as_u64() {
+        entry.field("hit_die", faces);
+        entry.labels.push(format!("**Hit Die:** d{}", faces));
+    }
+    entry.saving_throws(join(&class["proficiency"], |p| {
+        ability(&text(p)).unwrap_or_default().to_owned()
+    }));
+    entry.labelled(
+        "armor",
+        "Armor",
+        join(&class["startingProficiencies"]["armor"], |a| {
+            capitalize(&strip_tags(&text(a)))
+        }),
+    );
+    entry.labelled(
+        "weapons",
+        "Weapons",
+        join(&class["startingProficiencies"]["weapons"], |w| {
+            capitalize(&strip_tags(&text(w)))
+        }),
+    );
+    entry.field("source", text(&class["source"]));
+
+    let body = features
+        .iter()
+        .filter(|f| text(&f["className"]) == name)
+        .map(|f| feature_5etools(f))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    entry.body(body)
+}
+
+/// Convert a 5etools subclass, named after its class as in `Fighter: Champion`.
+fn subclass_5etools(subclass: &Value, features: &[&Value]) -> ImportedEntry {
+    let class = text(&subclass["className"]);
+    let short = text(&subclass["shortName"]);
+    let mut entry = ImportedEntry::new(
+        &format!("{}: {}", class, text(&subclass["name"])),
+        "subclass",
+    );
+    entry.field("class", class.clone());
+    entry.field("source", text(&subclass["source"]));
+
+    let body = features
+        .iter()
+        .filter(|f| text(&f["className"]) == class && text(&f["subclassShortName"]) == short)
+        .map(|f| feature_5etools(f))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    entry.body(body)
+}
+
+/// Convert a class or subclass feature into a section headed by its level and name.
+fn feature_5etools(feature: &Value) -> String {
+    format!(
+        "## Level {}: {}\n\n{}",
+        text(&feature["level"]),
+        text(&feature["name"]),
+        entries_5etools(&feature["entries"])
+    )
+}
+
+/// Convert a 5etools monster.
+///
+/// The whole stat block, including traits and actions, is kept as metadata.
+fn monster_5etools(monster: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&monster["name"]), "monster");
+    let size = join(&monster["size"], |s| size(&text(s)).to_owned());
+    let kind = match &monster["type"] {
+        Value::Object(kind) => text(&kind["type"]),
+        kind => text(kind),
+    };
+    let cr = match &monster["cr"] {
+        Value::Object(cr) => text(&cr["cr"]),
+        cr => text(cr),
+    };
+    let ac = match monster["ac"].get(0) {
+        Some(Value::Object(ac)) => ac["ac"].as_u64(),
+        Some(ac) => ac.as_u64(),
+        None => None,
+    };
+
+    entry.description_short = format!("{} {}, challenge {}", size, kind, cr);
+    entry.field("size", size);
+    entry.field("type", kind);
+    entry.field("alignment", alignment_5etools(&monster["alignment"]));
+    entry.field("cr", cr);
+    if let Some(ac) = ac {
+        entry.field("ac", ac);
+    }
+    if let Some(hp) = monster["hp"]["average"].as_u64() {
+        entry.field("hp", hp);
+        entry.field("hit_dice", text(&monster["hp"]["formula"]));
+    }
+    if let Value::Object(speed) = &monster["speed"] {
+        let mut speed = speed
+            .iter()
+            .filter_map(|(kind, feet)| {
+                let feet = match feet {
+                    Value::Object(feet) => feet["number"].as_u64()?,
+                    feet => feet.as_u64()?,
+                };
+                Some(match kind.as_str() {
+                    "walk" => format!("{} ft.", feet),
+                    kind => format!("{} {} ft.", kind, feet),
+                })
+            })
+            .collect::<Vec<_>>();
+        // The walking speed comes first, as in `40 ft., climb 30 ft.`
+        speed.sort_by_key(|s| !s.starts_with(|c: char| c.is_ascii_digit()));
+        entry.field("speed", speed.join(", "));
+    }
+    for stat in ["str", "dex", "con", "int", "wis", "cha"] {
+        entry.field(stat, monster[stat].clone());
+    }
+    entry.field("saving_throws", bonuses_5etools(&monster["save"]));
+    entry.field("skills", bonuses_5etools(&monster["skill"]));
+    entry.field(
+        "damage_resistances",
+        damage_5etools(&monster["resist"], "resist"),
+    );
+    entry.field(
+        "damage_immunities",
+        damage_5etools(&monster["immune"], "immune"),
+    );
+    entry.field(
+        "condition_immunities",
+        damage_5etools(&monster["conditionImmune"], "conditionImmune"),
+    );
+    let mut senses = join(&monster["senses"], |s| strip_tags(&text(s)));
+    if let Some(passive) = monster["passive"].as_u64() {
+        if !senses.is_empty() {
+            senses.push_str(", ");
+        }
+        senses.push_str(&format!("passive Perception {}", passive));
+    }
+    entry.field("senses", senses);
+    entry.field("languages", join(&monster["languages"], text));
+    for (key, field) in [
+        ("trait", "traits"),
+        ("action", "actions"),
+        ("bonus", "bonus_actions"),
+        ("reaction", "reactions"),
+        ("legendary", "legendary_actions"),
+    ] {
+        entry.field(field, features_5etools(&monster[key]));
+    }
+    entry.field("source", text(&monster["source"]));
+
+    entry
+}
+
+/// Describe 5etools alignment abbreviations, as in `chaotic evil`.
+fn alignment_5etools(alignment: &Value) -> String {
+    let words: Vec<&str> = alignment
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|a| {
+            Some(match a.as_str()? {
+                "L" => "lawful",
+                "N" | "NX" | "NY" => "neutral",
+                "C" => "chaotic",
+                "G" => "good",
+                "E" => "evil",
+                "U" => "unaligned",
+                "A" => "any alignment",
+                _ => return None,
+            })
+        })
+        .collect();
+    match words.as_slice() {
+        ["lawful", "neutral", "chaotic", "good", "neutral", "evil"] => {
+            String::from("any alignment")
+        }
+        words => words.join(" "),
+    }
+}
+
+/// Describe 5etools saving throw or skill bonuses, as in `Dex +4, Perception +3`.
+fn bonuses_5etools(bonuses: &Value) -> String {
+    let Value::Object(bonuses) = bonuses else {
+        return String::new();
+    };
+    bonuses
+        .iter()
+        .map(|(name, bonus)| format!("{} {}", capitalize(name), text(bonus)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describe 5etools damage resistances or immunities, including conditional ones such as
+/// `bludgeoning, piercing (from nonmagical attacks)` under the given key.
+fn damage_5etools(damage: &Value, key: &str) -> String {
+    join(damage, |d| match d {
+        Value::Object(d) if d.contains_key(key) => {
+            let types = damage_5etools(&d[key], key);
+            match d.get("note") {
+                Some(note) => format!("{} {}", types, strip_tags(&text(note))),
+                None => types,
+            }
+        }
+        Value::Object(d) => strip_tags(&text(&d["special"])),
+        d => text(d),
+    })
+}
+
+/// Convert 5etools monster traits or actions into named stat block features.
+fn features_5etools(features: &Value) -> Vec<Value> {
+    features
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|feature| {
+            serde_json::json!({
+                "name": strip_tags(&text(&feature["name"])),
+                "description": entries_5etools(&feature["entries"]),
+            })
+        })
+        .collect()
+}
+
+/// Describe a 5etools feat prerequisite.
+fn prerequisite_5etools(prerequisite: &Value) -> String {
+    join(prerequisite, |p| {
+        let Value::Object(p) = p else {
+            return text(p);
+        };
+        p.iter()
+            .map(|(kind, value)| match kind.as_str() {
+                "level" => match value {
+                    Value::Object(level) => format!("Level {}", text(&level["level"])),
+                    level => format!("Level {}", text(level)),
+                },
+                "ability" => join(value, |a| {
+                    let Value::Object(a) = a else {
+                        return String::new();
+                    };
+                    a.iter()
+                        .filter_map(|(stat, score)| Some(format!("{} {}", ability(stat)?, score)))
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                }),
+                "spellcasting" | "spellcasting2020" => String::from("Spellcasting"),
+                _ => capitalize(&join(value, |v| match v {
+                    Value::Object(v) => text(&v["name"]),
+                    v => strip_tags(&text(v)),
+                })),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
+
+/// Describe a 5etools spell range.
+fn range_5etools(range: &Value) -> String {
+    let distance = &range["distance"];
+    let unit = text(&distance["type"]);
+    let amount = distance["amount"].as_u64().unwrap_or_default();
+    match text(&range["type"]).as_str() {
+        "point" => match unit.as_str() {
+            "feet" | "miles" => count(amount, &unit.replace("feet", "foot")),
+            unit => capitalize(unit),
+        },
+        "special" => String::from("Special"),
+        shape => format!(
+            "Self ({}-{} {})",
+            amount,
+            unit.replace("feet", "foot").trim_end_matches('s'),
+            shape
+        ),
+    }
+}
+
+/// Convert 5etools entries into lookup markup.
+///
+/// Entries are strings, or objects such as named sections, lists and tables that
+/// hold more entries.
+fn entries_5etools(entries: &Value) -> String {
+    let mut blocks = vec![];
+    match entries {
+        Value::Array(entries) => entries.iter().for_each(|e| entry_5etools(e, &mut blocks)),
+        Value::Null => {}
+        entry => entry_5etools(entry, &mut blocks),
+    }
+    blocks.join("\n\n")
+}
+
+/// Convert a single 5etools entry into blocks of lookup markup.
+fn entry_5etools(entry: &Value, blocks: &mut Vec<String>) {
+    let Value::Object(object) = entry else {
+        let text = strip_tags(&text(entry));
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+        return;
+    };
+
+    match text(&entry["type"]).as_str() {
+        "list" => {
+            let items: Vec<String> = entry["items"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|item| {
+                    let body = match item {
+                        Value::Object(item) => {
+                            let body = match item.get("entry") {
+                                Some(entry) => entries_5etools(entry),
+                                None => entries_5etools(&item["entries"]),
+                            };
+                            match item.get("name") {
+                                Some(name) => format!("**{}** {}", text(name), body),
+                                None => body,
+                            }
+                        }
+                        item => strip_tags(&text(item)),
+                    };
+                    format!("- {}", body.replace("\n\n", " "))
+                })
+                .collect();
+            blocks.push(items.join("\n"));
+        }
+        "table" => {
+            let mut rows = vec![];
+            if let Some(caption) = entry["caption"].as_str() {
+                blocks.push(format!("**{}**", strip_tags(caption)));
+            }
+            if let Some(labels) = entry["colLabels"].as_array() {
+                rows.push(table_row(labels));
+                rows.push(format!("|{}", "---|".repeat(labels.len())));
+            }
+            for row in entry["rows"].as_array().into_iter().flatten() {
+                match row {
+                    Value::Array(cells) => rows.push(table_row(cells)),
+                    Value::Object(row) => rows.push(table_row(
+                        row["row"].as_array().map(Vec::as_slice).unwrap_or_default(),
+                    )),
+                    _ => {}
+                }
+            }
+            blocks.push(rows.join("\n"));
+        }
+        "image" | "gallery" => {}
+        "abilityDc" => blocks.push(format!(
+            "**Spell save DC** = 8 + your proficiency bonus + your {} modifier",
+            join(&entry["attributes"], |a| ability(&text(a))
+                .unwrap_or_default()
+                .to_owned())
+        )),
+        "abilityAttackMod" => blocks.push(format!(
+            "**Spell attack modifier** = your proficiency bonus + your {} modifier",
+            join(&entry["attributes"], |a| ability(&text(a))
+                .unwrap_or_default()
+                .to_owned())
+        )),
+        _ => {
+            let mut inner = vec![];
+            if let Some(entry) = object.get("entry") {
+                entry_5etools(entry, &mut inner);
+            }
+            for entry in entry["entries"].as_array().into_iter().flatten() {
+                entry_5etools(entry, &mut inner);
+            }
+
+            // Named sections lead into their first paragraph, as in `**At Higher Levels.** ...`
+            if let Some(name) = entry["name"].as_str() {
+                let name = format!("**{}.**", strip_tags(name).trim_end_matches('.'));
+                match inner.first_mut() {
+                    Some(first) if !first.starts_with(['-', '|', '#', '*']) => {
+                        *first = format!("{} {}", name, first)
+                    }
+                    _ => inner.insert(0, name),
+                }
+            }
+            blocks.extend(inner);
+        }
+    }
+}
+
+/// Convert a row of 5etools table cells into a markup table row.
+fn table_row(cells: &[Value]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            let text = match &cell["roll"] {
+                Value::Object(roll) => match (roll.get("min"), roll.get("max")) {
+                    (Some(min), Some(max)) => format!("{}-{}", min, max),
+                    _ => text(&roll["exact"]),
+                },
+                _ => match cell {
+                    Value::Object(_) => entries_5etools(cell),
+                    cell => strip_tags(&text(cell)),
+                },
+            };
+            text.replace('|', "/").replace('\n', " ")
+        })
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Replace 5etools `{@tag text|source|shown text}` tags with plain text or markup.
+///
+/// Tags that refer to spells, items, conditions and the like become links.
+fn strip_tags(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{@") {
+        out.push_str(&rest[..start]);
+
+        let mut depth = 0;
+        let mut end = None;
+        for (i, c) in rest[start..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(start + i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            // An unclosed tag is kept as it is
+            rest = &rest[start..];
+            break;
+        };
+
+        let inner = strip_tags(&rest[start + 2..end]);
+        let (tag, body) = inner.split_once(' ').unwrap_or((&inner, ""));
+        let parts: Vec<&str> = body.split('|').collect();
+        let shown = parts
+            .get(2)
+            .filter(|s| !s.is_empty())
+            .copied()
+            .unwrap_or(parts[0]);
+        out.push_str(&match tag {
+            "b" | "bold" => format!("**{}**", parts[0]),
+            "i" | "italic" => format!("*{}*", parts[0]),
+            "dc" => format!("DC {}", parts[0]),
+            "hit" if !parts[0].starts_with(['+', '-']) => format!("+{}", parts[0]),
+            "h" => String::from("*Hit:* "),
+            "atk" => match parts[0] {
+                "mw" => String::from("*Melee Weapon Attack:*"),
+                "rw" => String::from("*Ranged Weapon Attack:*"),
+                "mw,rw" => String::from("*Melee or Ranged Weapon Attack:*"),
+                "ms" => String::from("*Melee Spell Attack:*"),
+                "rs" => String::from("*Ranged Spell Attack:*"),
+                _ => String::from("*Attack:*"),
+            },
+            "recharge" if parts[0].is_empty() => String::from("(Recharge 6)"),
+            "recharge" => format!("(Recharge {}-6)", parts[0]),
+            "filter" => parts[0].to_owned(),
+            tag if LINK_TAGS.contains(&tag) && shown == parts[0] => format!("[[{}]]", shown),
+            tag if LINK_TAGS.contains(&tag) => format!("[[{}|{}]]", parts[0], shown),
+            _ => shown.to_owned(),
+        });
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Import a list of Open5e or SRD API results.
+///
+/// The kind of each result is guessed from its fields, falling back to the category
+/// of the input file.
+fn import_open5e(results: &[Value], category: &str, lookup: &mut ImportedLookup) {
+    for result in results {
+        if !result.is_object() {
+            continue;
+        }
+        let has = |key: &str| result.get(key).is_some_and(|v| !v.is_null());
+
+        let mut entry = if has("school") && (has("casting_time") || has("level")) {
+            spell_open5e(result)
+        } else if has("damage_dice") || has("weapon_category") {
+            weapon_open5e(result)
+        } else if has("base_ac") || has("armor_category") {
+            armor_open5e(result)
+        } else if has("challenge_rating") {
+            monster_open5e(result)
+        } else if has("rarity") {
+            let rarity = text(&result["rarity"]);
+            let category = match rarity.to_lowercase().as_str() {
+                "" | "none" | "mundane" => "item",
+                _ => "magic item",
+            };
+            let mut entry = ImportedEntry::new(&text(&result["name"]), category);
+            let attunement = text(&result["requires_attunement"]);
+            entry.description_short = [text(&result["type"]), rarity.clone()]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ");
+            if !attunement.is_empty() {
+                entry.description_short = format!("{} ({})", entry.description_short, attunement);
+            }
+            entry.field("rarity", rarity.to_lowercase());
+            entry.field("attunement", attunement);
+            if let Some((charges, recharge)) = charges(&text(&result["desc"])) {
+                entry.field("charges", charges);
+                entry.field("recharge", recharge);
+            }
+            entry
+        } else if has("hit_dice") || has("hit_die") {
+            let mut entry = ImportedEntry::new(&text(&result["name"]), "class");
+            let faces = match &result["hit_die"] {
+                Value::Number(faces) => faces.as_u64(),
+                _ => text(&result["hit_dice"])
+                    .split_once('d')
+                    .and_then(|(_, faces)| faces.parse().ok()),
+            };
+            if let Some(faces) = faces {
+                entry.field("hit_die", faces);
+                entry.labels.push(format!("**Hit Die:** d{}", faces));
+            }
+            entry.saving_throws(match &result["saving_throws"] {
+                Value::Null => text(&result["prof_saving_throws"]),
+                saves => text(saves),
+            });
+            entry
+        } else if has("asi_desc") || has("ability_bonuses") {
+            let mut entry = ImportedEntry::new(&text(&result["name"]), "race");
+            entry.labelled("size", "Size", text(&result["size_raw"]));
+            entry.labelled("speed", "Speed", text(&result["speed_desc"]));
+            entry
+        } else if has("prerequisite") {
+            let mut entry = ImportedEntry::new(&text(&result["name"]), "feat");
+            entry.labelled(
+                "prerequisite",
+                "Prerequisite",
+                text(&result["prerequisite"]),
+            );
+            entry
+        } else {
+            ImportedEntry::new(&text(&result["name"]), category)
+        };
+
+        let source = match &result["document__title"] {
+            Value::Null => String::from("SRD"),
+            title => text(title),
+        };
+        entry.field("source", source);
+
+        let mut body = paragraphs(&result["desc"]);
+        for key in ["higher_level", "asi_desc", "traits"] {
+            let text = paragraphs(&result[key]);
+            if !text.is_empty() {
+                body = format!("{}\n\n{}", body, text);
+            }
+        }
+        let effects: Vec<String> = result["effects_desc"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|e| format!("- {}", text(e)))
+            .collect();
+        if !effects.is_empty() {
+            body = format!("{}\n\n{}", body, effects.join("\n"));
+        }
+        for (key, heading) in [
+            ("special_abilities", ""),
+            ("actions", "Actions"),
+            ("bonus_actions", "Bonus Actions"),
+            ("reactions", "Reactions"),
+            ("legendary_actions", "Legendary Actions"),
+        ] {
+            let Some(Value::Array(actions)) = result.get(key) else {
+                continue;
+            };
+            let actions: Vec<String> = actions
+                .iter()
+                .map(|a| format!("**{}.** {}", text(&a["name"]), paragraphs(&a["desc"])))
+                .collect();
+            if actions.is_empty() {
+                continue;
+            }
+            if !heading.is_empty() {
+                body = format!("{}\n\n## {}", body, heading);
+            }
+            body = format!("{}\n\n{}", body, actions.join("\n\n"));
+        }
+        lookup.add(entry.body(body.trim().to_owned()));
+    }
+}
+
+/// Convert an Open5e or SRD spell.
+fn spell_open5e(spell: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&spell["name"]), "spell");
+    let level = match (&spell["level_int"], &spell["level"]) {
+        (Value::Number(level), _) | (_, Value::Number(level)) => level.as_u64().unwrap_or_default(),
+        (_, level) => text(level)
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .unwrap_or_default(),
+    };
+    let school = capitalize(&text(&spell["school"]));
+    let ritual = flag(&spell["ritual"]);
+    let concentration = flag(&spell["concentration"]);
+
+    entry.description_short = spell_level(level, &school, ritual);
+    entry.field("level", level);
+    entry.field("school", school);
+    entry.labelled("casting_time", "Casting Time", text(&spell["casting_time"]));
+    entry.labelled("range", "Range", text(&spell["range"]));
+    let listed = text(&spell["components"]);
+    entry.labelled(
+        "components",
+        "Components",
+        components(
+            listed.contains('V'),
+            listed.contains('S'),
+            match &spell["material"] {
+                Value::Null if listed.contains('M') => String::from(" "),
+                material => text(material),
+            },
+        ),
+    );
+    let duration = text(&spell["duration"]);
+    entry.labelled(
+        "duration",
+        "Duration",
+        if concentration && !duration.to_lowercase().starts_with("concentration") {
+            format!("Concentration, {}", duration)
+        } else {
+            duration
+        },
+    );
+    entry.field("ritual", ritual);
+    entry.field("concentration", concentration);
+    let classes: Vec<Value> = match &spell["classes"] {
+        Value::Array(classes) => classes.iter().map(|c| Value::from(text(c))).collect(),
+        _ => text(&spell["dnd_class"])
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(Value::from)
+            .collect(),
+    };
+    entry.field("classes", classes);
+    entry
+}
+
+/// Convert an Open5e or SRD weapon.
+fn weapon_open5e(weapon: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&weapon["name"]), "weapon");
+    let category = match &weapon["category"] {
+        Value::Null => text(&weapon["weapon_category"]),
+        category => text(category),
+    };
+    entry.description_short = category.clone();
+    entry.field("weapon_category", category);
+    entry.labelled("cost", "Cost", text(&weapon["cost"]));
+    entry.labelled("weight", "Weight", weight(&weapon["weight"]));
+    let damage = match &weapon["damage"] {
+        Value::Object(damage) => format!(
+            "{} {}",
+            text(&damage["damage_dice"]),
+            text(&damage["damage_type"])
+        ),
+        _ => format!(
+            "{} {}",
+            text(&weapon["damage_dice"]),
+            text(&weapon["damage_type"])
+        ),
+    };
+    entry.labelled("damage", "Damage", damage.trim().to_lowercase());
+    let properties: Vec<Value> = weapon["properties"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|p| Value::from(text(p).to_lowercase()))
+        .collect();
+    if !properties.is_empty() {
+        entry.labels.push(format!(
+            "**Properties:** {}",
+            properties.iter().map(text).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    entry.field("properties", properties);
+    entry
+}
+
+/// Convert an Open5e or SRD armor.
+fn armor_open5e(armor: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&armor["name"]), "armor");
+    let category = match &armor["category"] {
+        Value::Null => text(&armor["armor_category"]),
+        category => text(category),
+    };
+    let ac = match &armor["armor_class"] {
+        Value::Object(ac) => ac["base"].as_u64(),
+        _ => armor["base_ac"].as_u64(),
+    };
+    entry.description_short = category.clone();
+    entry.field("armor_category", category);
+    entry.field("ac", ac);
+    entry.labelled("cost", "Cost", text(&armor["cost"]));
+    entry.labelled("weight", "Weight", weight(&armor["weight"]));
+    match &armor["ac_string"] {
+        Value::String(ac) => entry.labels.push(format!("**Armor Class:** {}", ac)),
+        _ => {
+            if let Some(ac) = ac {
+                entry.labels.push(format!("**Armor Class:** {}", ac));
+            }
+        }
+    }
+    let strength = match &armor["str_minimum"] {
+        Value::Null => armor["strength_requirement"].as_u64(),
+        strength => strength.as_u64(),
+    };
+    if let Some(strength) = strength.filter(|s| *s > 0) {
+        entry.field("strength", strength);
+        entry.labels.push(format!("**Strength:** {}", strength));
+    }
+    if flag(&armor["stealth_disadvantage"]) {
+        entry.field("stealth_disadvantage", true);
+        entry.labels.push(String::from("**Stealth:** Disadvantage"));
+    }
+    entry
+}
+
+/// Convert an Open5e or SRD monster.
+///
+/// The whole stat block, including traits and actions, is kept as metadata.
+fn monster_open5e(monster: &Value) -> ImportedEntry {
+    let mut entry = ImportedEntry::new(&text(&monster["name"]), "monster");
+    let size = text(&monster["size"]);
+    let kind = text(&monster["type"]).to_lowercase();
+    let cr = text(&monster["challenge_rating"]);
+    let ac = match &monster["armor_class"] {
+        Value::Array(ac) => ac.first().and_then(|ac| ac["value"].as_u64()),
+        ac => ac.as_u64(),
+    };
+
+    entry.description_short = format!("{} {}, challenge {}", size, kind, cr);
+    entry.field("size", size);
+    entry.field("type", kind);
+    entry.field("alignment", text(&monster["alignment"]));
+    entry.field("cr", cr);
+    if let Some(ac) = ac {
+        entry.field("ac", ac);
+    }
+    if let Some(hp) = monster["hit_points"].as_u64() {
+        entry.field("hp", hp);
+        entry.field("hit_dice", text(&monster["hit_dice"]));
+    }
+    if let Value::Object(speed) = &monster["speed"] {
+        let mut speed = speed
+            .iter()
+            .filter_map(|(kind, feet)| {
+                let feet = text(feet);
+                let feet = feet.trim_end_matches(" ft.");
+                match kind.as_str() {
+                    "walk" => Some(format!("{} ft.", feet)),
+                    "hover" => None,
+                    kind => Some(format!("{} {} ft.", kind, feet)),
+                }
+            })
+            .collect::<Vec<_>>();
+        // The walking speed comes first, as in `40 ft., climb 30 ft.`
+        speed.sort_by_key(|s| !s.starts_with(|c: char| c.is_ascii_digit()));
+        entry.field("speed", speed.join(", "));
+    }
+    let mut saves = vec![];
+    for (stat, key) in [
+        ("str", "strength"),
+        ("dex", "dexterity"),
+        ("con", "constitution"),
+        ("int", "intelligence"),
+        ("wis", "wisdom"),
+        ("cha", "charisma"),
+    ] {
+        entry.field(stat, monster[key].clone());
+        if let Some(save) = monster[format!("{}_save", key)].as_i64() {
+            saves.push(format!("{} {:+}", capitalize(stat), save));
+        }
+    }
+    entry.field("saving_throws", saves.join(", "));
+    if let Value::Object(skills) = &monster["skills"] {
+        let skills = skills
+            .iter()
+            .filter_map(|(skill, bonus)| {
+                Some(format!("{} {:+}", capitalize(skill), bonus.as_i64()?))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        entry.field("skills", skills);
+    }
+    for key in [
+        "damage_resistances",
+        "damage_immunities",
+        "condition_immunities",
+        "senses",
+        "languages",
+    ] {
+        entry.field(key, text(&monster[key]));
+    }
+    for (key, field) in [
+        ("special_abilities", "traits"),
+        ("actions", "actions"),
+        ("reactions", "reactions"),
+        ("legendary_actions", "legendary_actions"),
+    ] {
+        let features: Vec<Value> = monster[key]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|feature| {
+                serde_json::json!({
+                    "name": text(&feature["name"]),
+                    "description": text(&feature["desc"]),
+                })
+            })
+            .collect();
+        entry.field(field, features);
+    }
+    entry
+}
+
+/// Describe a spell's level and school as in `3rd-level evocation` or `Evocation cantrip`.
+fn spell_level(level: u64, school: &str, ritual: bool) -> String {
+    let level = match level {
+        0 => format!("{} cantrip", capitalize(school)),
+        1 => format!("1st-level {}", school.to_lowercase()),
+        2 => format!("2nd-level {}", school.to_lowercase()),
+        3 => format!("3rd-level {}", school.to_lowercase()),
+        n => format!("{}th-level {}", n, school.to_lowercase()),
+    };
+    if ritual {
+        format!("{} (ritual)", level)
+    } else {
+        level
+    }
+}
+
+/// Describe spell components as in `V, S, M (a bit of fleece)`.
+///
+/// A blank `material` means a material component with no description.
+fn components(verbal: bool, somatic: bool, material: String) -> String {
+    let mut components = vec![];
+    if verbal {
+        components.push(String::from("V"));
+    }
+    if somatic {
+        components.push(String::from("S"));
+    }
+    match material.trim() {
+        _ if material.is_empty() => {}
+        "" => components.push(String::from("M")),
+        material => components.push(format!("M ({})", strip_tags(material))),
+    }
+    components.join(", ")
+}
+
+/// Describe a cost given in copper pieces using the largest whole coin.
+fn cost(copper: f64) -> String {
+    if copper >= 100.0 && copper % 100.0 == 0.0 {
+        format!("{} gp", copper / 100.0)
+    } else if copper >= 10.0 && copper % 10.0 == 0.0 {
+        format!("{} sp", copper / 10.0)
+    } else {
+        format!("{} cp", copper)
+    }
+}
+
+/// Describe a weight given as a number of pounds or as text.
+fn weight(weight: &Value) -> String {
+    match weight {
+        Value::Number(weight) => format!("{} lb.", weight),
+        weight => text(weight),
+    }
+}
+
+/// Find the charges of a magic item and how many it regains in its description, as in
+/// `The wand has 7 charges. ... The wand regains 1d6 + 1 expended charges daily at dawn.`
+fn charges(description: &str) -> Option<(u64, String)> {
+    let lower = description.to_lowercase();
+    let (before, _) = lower.split_once(" charges")?;
+    let charges = before.split_whitespace().last()?.parse().ok()?;
+
+    let recharge = lower
+        .split_once("regains ")
+        .and_then(|(_, rest)| rest.split_once('.'))
+        .map(|(rule, _)| {
+            let (amount, when) = rule.split_once("expended charge").unwrap_or((rule, ""));
+            let when = when.trim_start_matches('s').trim();
+            let when = when.strip_prefix("daily ").unwrap_or(when);
+            format!("{} {}", amount.trim(), when).trim().to_owned()
+        })
+        .unwrap_or_default();
+    Some((charges, recharge))
+}
+
+/// Get the full name of a size abbreviation.
+fn size(size: &str) -> &str {
+    match size {
+        "T" => "Tiny",
+        "S" => "Small",
+        "M" => "Medium",
+        "L" => "Large",
+        "H" => "Huge",
+        "G" => "Gargantuan",
+        size => size,
+    }
+}
+
+/// Get the full name of an ability abbreviation, if it is one.
+fn ability(ability: &str) -> Option<&'static str> {
+    match ability {
+        "str" => Some("Strength"),
+        "dex" => Some("Dexterity"),
+        "con" => Some("Constitution"),
+        "int" => Some("Intelligence"),
+        "wis" => Some("Wisdom"),
+        "cha" => Some("Charisma"),
+        _ => None,
+    }
+}
+
+/// Describe an amount of a unit, as in `1 action` or `10 minutes`.
+fn count(amount: u64, unit: &str) -> String {
+    if amount == 1 || unit.is_empty() {
+        format!("{} {}", amount, unit).trim().to_owned()
+    } else if unit == "foot" {
+        format!("{} feet", amount)
+    } else if unit.ends_with('s') {
+        format!("{} {}", amount, unit)
+    } else {
+        format!("{} {}s", amount, unit)
+    }
+}
+
+/// Uppercase the first letter of some text.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether a value is `true` or text such as `yes`.
+fn flag(value: &Value) -> bool {
+    match value {
+        Value::Bool(flag) => *flag,
+        Value::String(flag) => matches!(flag.to_lowercase().as_str(), "yes" | "true"),
+        _ => false,
+    }
+}
+
+/// Describe each value of an array, or a single value, joined by commas.
+fn join(values: &Value, describe: impl Fn(&Value) -> String) -> String {
+    let described: Vec<String> = match values {
+        Value::Array(values) => values.iter().map(&describe).collect(),
+        Value::Null => vec![],
+        value => vec![describe(value)],
+    };
+    described
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Join text given as a string or a list of paragraphs.
+fn paragraphs(value: &Value) -> String {
+    match value {
+        Value::Array(paragraphs) => paragraphs.iter().map(text).collect::<Vec<_>>().join("\n\n"),
+        value => text(value),
+    }
+}
+
+/// Get a value as plain text.
+///
+/// Objects such as `{ "name": "Wizard" }` or `{ "quantity": 2, "unit": "gp" }` are
+/// described by their name or quantity, and lists are joined by commas.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Array(_) => join(value, text),
+        Value::Object(object) => match (object.get("quantity"), object.get("unit")) {
+            (Some(quantity), Some(unit)) => format!("{} {}", text(quantity), text(unit)),
+            _ => object.get("name").map(text).unwrap_or_default(),
+        },
+        Value::Null => String::new(),
+    }
+}
@@ -21,3 +21,12 @@ pub mod player;
 
 /// Lookup spell and item descriptions
 pub mod lookup;
+
+/// Lightweight markup for lookup descriptions
+pub mod markup;
+
+/// Convert other tools' data into lookup files
+pub mod import;
+
+/// Typed mechanics for lookup entries
+pub mod metadata;
@@ -1,15 +1,163 @@
-use color_eyre::eyre::{Result, WrapErr};
-use serde_derive::Deserialize;
-use std::{collections::HashMap, path::PathBuf, rc::Rc};
+use color_eyre::eyre::{eyre, Report, Result, WrapErr};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-#[derive(Deserialize, Default, Clone)]
+use crate::{core::backups::write_atomic, metadata::Metadata};
+
+/// Binary cache of loaded lookups
+mod cache;
+
+/// The extensions of files that hold lookup tables.
+const LOOKUP_EXTENSIONS: [&str; 4] = ["json", "yaml", "yml", "toml"];
+
+/// The lookup file that entries written in the homebrew editor are saved to.
+const HOMEBREW_FILE: &str = "homebrew.yaml";
+
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct LookupEntry {
     #[serde(default)]
     pub name: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub description_short: String,
     #[serde(default)]
     pub description: String,
+    /// The kind of entry, such as `spell` or `weapon`.
+    ///
+    /// Entries without a category take the category of their lookup file.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub category: String,
+    /// The books the entry is published in, separated by `/` or `,`.
+    ///
+    /// Entries without a source take it from a `Source:` line at the start of their text.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub source: String,
+    /// Whether the entry is homebrew rather than published content.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub homebrew: bool,
+    /// Typed mechanics of the entry, such as a spell's level, if any.
+    #[serde(flatten)]
+    pub metadata: Option<Metadata>,
+}
+
+impl LookupEntry {
+    /// Get the short description followed by the full description, as shown in the lookup popup.
+    ///
+    /// Monsters show their stat block between the two.
+    pub fn full_text(&self) -> String {
+        match &self.metadata {
+            Some(Metadata::Monster(monster)) => format!(
+                "{}\n{}\n\n{}",
+                self.description_short,
+                monster.stat_block(),
+                self.description
+            )
+            .trim_end()
+            .to_owned(),
+            _ => format!("{}\n{}", self.description_short, self.description),
+        }
+    }
+
+    /// Check whether the entry matches every `key:value` filter.
+    ///
+    /// The `category` key matches the entry category, with `-` or `_` in place of spaces,
+    /// and `homebrew:yes` matches homebrew entries. Any other key matches a field of the
+    /// entry metadata.
+    pub fn matches(&self, filters: &[(String, String)]) -> bool {
+        filters.iter().all(|(key, value)| match key.as_str() {
+            "category" => {
+                // Filters can't hold spaces, so `magic-item` matches `magic item`
+                let words = |c: &str| c.replace(['-', '_'], " ").to_lowercase();
+                words(&self.category) == words(value)
+            }
+            "homebrew" => matches!(
+                (self.homebrew, value.as_str()),
+                (true, "true" | "yes") | (false, "false" | "no")
+            ),
+            key => self
+                .metadata
+                .as_ref()
+                .is_some_and(|m| m.matches(key, value)),
+        })
+    }
+
+    /// Check whether the entry comes from one of the allowed sources.
+    ///
+    /// Sources match by their full name or abbreviation, so `PHB` allows entries from the
+    /// Player's Handbook. Everything is allowed if the list is empty, as are entries
+    /// without a source.
+    pub fn is_allowed(&self, allowed: &[String]) -> bool {
+        if allowed.is_empty() || self.source.trim().is_empty() {
+            return true;
+        }
+        self.source
+            .split(['/', ','])
+            .map(source_key)
+            .any(|source| allowed.iter().any(|a| source_key(a) == source))
+    }
+
+    /// Find the source named by a `Source:` line near the start of the entry text.
+    fn source_line(&self) -> Option<String> {
+        let text = [&self.description_short, &self.description];
+        text.iter()
+            .flat_map(|t| t.lines().take(3))
+            .find_map(|line| line.trim().strip_prefix("Source:"))
+            .map(|source| source.trim().to_owned())
+    }
+}
+
+/// Common sourcebooks and their abbreviations.
+const SOURCE_ABBREVIATIONS: [(&str, &str); 24] = [
+    ("phb", "player's handbook"),
+    ("dmg", "dungeon master's guide"),
+    ("mm", "monster manual"),
+    ("xge", "xanathar's guide to everything"),
+    ("tce", "tasha's cauldron of everything"),
+    ("scag", "sword coast adventurer's guide"),
+    ("vgm", "volo's guide to monsters"),
+    ("mtf", "mordenkainen's tome of foes"),
+    ("mpmm", "mordenkainen presents: monsters of the multiverse"),
+    ("ftd", "fizban's treasury of dragons"),
+    ("egw", "explorer's guide to wildemount"),
+    ("erlw", "eberron: rising from the last war"),
+    ("ggr", "guildmaster's guide to ravnica"),
+    ("mot", "mythic odysseys of theros"),
+    ("vrgr", "van richten's guide to ravenloft"),
+    ("scc", "strixhaven: a curriculum of chaos"),
+    (
+        "aag",
+        "spelljammer: adventures in space - astral adventurer's guide",
+    ),
+    ("idrotf", "icewind dale - rime of the frostmaiden"),
+    ("eepc", "elemental evil player's companion"),
+    ("ai", "acquisitions inc."),
+    ("llk", "lost laboratory of kwalish"),
+    ("psa", "plane shift - amonkhet"),
+    ("xphb", "player's handbook (2024)"),
+    ("srd", "systems reference document"),
+];
+
+/// Get the lowercase abbreviation of a source, or its lowercase name if it has none.
+fn source_key(source: &str) -> String {
+    let source = source.trim().to_lowercase();
+    SOURCE_ABBREVIATIONS
+        .iter()
+        .find(|(_, name)| *name == source)
+        .map_or(source, |(abbreviation, _)| (*abbreviation).to_owned())
+}
+
+/// The contents of a lookup file, as written by the homebrew editor.
+#[derive(Serialize, Deserialize, Default)]
+struct LookupFile {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    category: String,
+    #[serde(default)]
+    entries: BTreeMap<String, LookupEntry>,
 }
 
 #[derive(Deserialize, Default)]
@@ -19,6 +167,26 @@ pub struct Lookup {
     pub loaded: bool,
     #[serde(default)]
     load_path: PathBuf,
+    /// The category of every entry in a lookup file.
+    ///
+    /// Defaults to the singular form of the file name, so `spells.json` holds `spell` entries.
+    #[serde(default)]
+    category: String,
+    /// An inverted index from each word to the entries containing it and how often it appears.
+    #[serde(skip)]
+    index: HashMap<String, Vec<(Rc<LookupEntry>, u32)>>,
+    /// The most words in the name of any entry.
+    #[serde(skip)]
+    name_words: usize,
+    /// Problems found while loading that have not been reported yet.
+    #[serde(skip)]
+    diagnostics: Vec<String>,
+    /// The file that loaded lookups are cached in, if any.
+    #[serde(skip)]
+    cache_path: Option<PathBuf>,
+    /// Folders in the load path that are not markdown lookups, such as the characters folder.
+    #[serde(skip)]
+    ignored_dirs: Vec<PathBuf>,
 }
 
 impl Lookup {
@@ -40,14 +208,94 @@ impl Lookup {
             entries: HashMap::new(),
             loaded: false,
             load_path,
+            category: String::new(),
+            index: HashMap::new(),
+            name_words: 0,
+            diagnostics: vec![],
+            cache_path: None,
+            ignored_dirs: vec![],
         }
     }
 
+    /// Cache loaded lookups in the given file, so later loads can skip parsing every
+    /// lookup file as long as none of them have changed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use scrim::lookup::Lookup;
+    ///
+    /// let mut lookup = Lookup::new("data/lookups".into()).with_cache("data/lookups.cache".into());
+    /// lookup.load().expect("failed to load lookup");
+    /// ```
+    pub fn with_cache(mut self, cache_path: PathBuf) -> Self {
+        self.cache_path = Some(cache_path);
+        self
+    }
+
+    /// Set the folders in the load path that should not be loaded as markdown lookups.
+    ///
+    /// In release builds, lookups share the `.scrim` folder with the characters folder.
+    pub fn set_ignored_dirs(&mut self, dirs: Vec<PathBuf>) {
+        self.ignored_dirs = dirs;
+    }
+
+    /// Whether a folder is one of the ignored folders.
+    fn is_ignored_dir(&self, dir: &Path) -> bool {
+        let dir = std::fs::canonicalize(dir).unwrap_or(dir.to_owned());
+        self.ignored_dirs
+            .iter()
+            .any(|d| std::fs::canonicalize(d).unwrap_or(d.clone()) == dir)
+    }
+
     /// Load all lookup tables located in the directory specified by the load path.
+    ///
+    /// Files that cannot be read or parsed are skipped, as are entries already defined
+    /// by an earlier file. These problems are kept for `Lookup::take_diagnostics()`.
+    ///
+    /// If a cache file is set and none of the lookup files have changed since it was
+    /// written, the cached entries are used instead. Otherwise the cache is rebuilt.
     pub fn load(&mut self) -> Result<()> {
         if self.loaded {
             return Ok(());
         }
+        let paths = self.lookup_paths()?;
+        let files = cache::fingerprint(&paths);
+
+        if let Some(cache_path) = self.cache_path.clone() {
+            if self.read_cache(&cache_path, &files) {
+                self.loaded = true;
+                return Ok(());
+            }
+        }
+
+        let mut sources: HashMap<String, PathBuf> = HashMap::new();
+        for path in paths {
+            let lookups = if path.is_dir() {
+                Self::read_markdown_dir(&path)
+            } else {
+                vec![(path.clone(), Self::read_file(&path))]
+            };
+
+            for (path, lookup) in lookups {
+                match lookup {
+                    Ok(lookup) => self.add_lookup(&path, lookup, &mut sources),
+                    Err(e) => self.diagnostics.push(format!("{:#}", e)),
+                }
+            }
+        }
+
+        self.build_index();
+        if let Some(cache_path) = self.cache_path.clone() {
+            if let Err(e) = self.write_cache(&cache_path, files, self.diagnostics.clone()) {
+                self.diagnostics.push(format!("{:#}", e));
+            }
+        }
+        self.loaded = true;
+        Ok(())
+    }
+
+    /// Get the lookup files and markdown directories in the load path, in the order they are loaded.
+    fn lookup_paths(&self) -> Result<Vec<PathBuf>> {
         let files = std::fs::read_dir(self.load_path.as_path()).wrap_err_with(|| {
             format!(
                 "failed to read lookups from '{}'",
@@ -55,12 +303,13 @@ impl Lookup {
             )
         })?;
 
-        files
-            // filter for only JSON files
+        let mut paths: Vec<PathBuf> = files
+            // filter for lookup files and markdown directories
             .filter_map(|f| match f {
                 Ok(file) => {
                     let path = file.path();
-                    if path.is_file() && path.extension().unwrap_or_default() == "json" {
+                    let markdown_dir = path.is_dir() && !self.is_ignored_dir(&path);
+                    if markdown_dir || Self::is_lookup_file(&path) {
                         Some(path)
                     } else {
                         None
@@ -68,31 +317,281 @@ impl Lookup {
                 }
                 Err(_) => None,
             })
-            // try to parse each file
-            .for_each(|path| {
-                let lookup: Lookup = serde_json::from_slice(
-                    std::fs::read(&path)
-                        .wrap_err_with(|| {
-                            format!(
-                                "failed to read lookup file '{}'",
-                                path.to_str().unwrap_or(&path.to_string_lossy())
-                            )
-                        })
-                        .unwrap()
-                        .as_slice(),
+            .collect();
+        // Load in a fixed order so the same duplicate always wins
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Check whether a file is a JSON, YAML or TOML lookup file, judging by its name.
+    pub fn is_lookup_file(path: &Path) -> bool {
+        let name = path.file_name().unwrap_or_default();
+        let ext = path.extension().unwrap_or_default();
+        // The app config shares the `.scrim` folder with lookups in release builds
+        LOOKUP_EXTENSIONS.iter().any(|e| ext == *e) && name != "config.yaml"
+    }
+
+    /// Forget every loaded entry and load all lookup tables again.
+    ///
+    /// Problems with the reloaded files are kept for `Lookup::take_diagnostics()`.
+    pub fn reload(&mut self) -> Result<()> {
+        self.entries.clear();
+        self.index.clear();
+        self.loaded = false;
+        self.load()
+    }
+
+    /// Save an entry to the homebrew lookup file and make it available right away.
+    ///
+    /// `original` is the name the entry had before it was edited, if it already existed.
+    /// Entries from other lookup files, and other homebrew entries, are never replaced, so a
+    /// homebrew version of one needs a different name.
+    pub fn save_homebrew(&mut self, original: Option<&str>, entry: LookupEntry) -> Result<()> {
+        if !self.loaded {
+            self.load()?;
+        }
+
+        let path = self.load_path.join(HOMEBREW_FILE);
+        let mut file: LookupFile = if path.exists() {
+            let text = std::fs::read_to_string(&path).wrap_err_with(|| {
+                format!(
+                    "failed to read lookup file '{}'",
+                    path.to_str().unwrap_or(&path.to_string_lossy())
+                )
+            })?;
+            serde_yaml::from_str(&text).wrap_err_with(|| {
+                format!(
+                    "failed to parse lookup file '{}'",
+                    path.to_str().unwrap_or(&path.to_string_lossy())
                 )
-                .wrap_err_with(|| {
+            })?
+        } else {
+            LookupFile::default()
+        };
+
+        let key = entry.name.trim().to_lowercase();
+        if key.is_empty() {
+            return Err(eyre!("homebrew entries need a name"));
+        }
+        if entry.category.trim().is_empty() {
+            return Err(eyre!(
+                "homebrew entries need a category, such as 'spell' or 'item'"
+            ));
+        }
+        if self.entries.contains_key(&key) && !file.entries.contains_key(&key) {
+            return Err(eyre!(
+                "'{}' is already defined in another lookup file, give the homebrew version a different name",
+                entry.name.trim()
+            ));
+        }
+        let renamed = original.map(str::to_lowercase).as_deref() != Some(key.as_str());
+        if renamed && file.entries.contains_key(&key) {
+            return Err(eyre!(
+                "there is already a homebrew entry named '{}', give this one a different name",
+                entry.name.trim()
+            ));
+        }
+
+        if let Some(original) = original.map(str::to_lowercase) {
+            if file.entries.remove(&original).is_some() {
+                self.entries.remove(&original);
+            }
+        }
+        file.entries.insert(key.clone(), entry.clone());
+
+        let text = serde_yaml::to_string(&file)?;
+        write_atomic(&path, &text).wrap_err_with(|| {
+            format!(
+                "failed to save homebrew to '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+
+        let mut entry = entry;
+        if entry.source.is_empty() {
+            entry.source = entry.source_line().unwrap_or_default();
+        }
+        self.entries.insert(key, Rc::new(entry));
+        self.build_index();
+        Ok(())
+    }
+
+    /// Add the entries of a loaded lookup file, skipping any that were already added.
+    ///
+    /// `sources` holds the file each entry was added from.
+    fn add_lookup(
+        &mut self,
+        path: &Path,
+        mut lookup: Lookup,
+        sources: &mut HashMap<String, PathBuf>,
+    ) {
+        if lookup.category.is_empty() {
+            lookup.category = file_category(path);
+        }
+        for (key, mut entry) in lookup.entries {
+            if let Some(first) = sources.get(&key) {
+                self.diagnostics.push(format!(
+                    "duplicate entry '{}' in '{}' was skipped, it is already defined in '{}'",
+                    key,
+                    path.to_string_lossy(),
+                    first.to_string_lossy()
+                ));
+                continue;
+            }
+            if entry.category.is_empty() {
+                Rc::make_mut(&mut entry).category = lookup.category.clone();
+            }
+            if entry.source.is_empty() {
+                if let Some(source) = entry.source_line() {
+                    Rc::make_mut(&mut entry).source = source;
+                }
+            }
+            sources.insert(key.clone(), path.to_path_buf());
+            self.entries.insert(key, entry);
+        }
+    }
+
+    /// Read and parse a single JSON, YAML or TOML lookup file.
+    fn read_file(path: &Path) -> Result<Lookup> {
+        let text = std::fs::read_to_string(path).wrap_err_with(|| {
+            format!(
+                "failed to read lookup file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+
+        let ext = path.extension().unwrap_or_default();
+        let lookup = match ext.to_str().unwrap_or_default() {
+            "yaml" | "yml" => serde_yaml::from_str(&text).map_err(Report::new),
+            "toml" => toml::from_str(&text).map_err(Report::new),
+            _ => serde_json::from_str(&text).map_err(Report::new),
+        };
+
+        lookup.wrap_err_with(|| {
+            format!(
+                "failed to parse lookup file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })
+    }
+
+    /// Read every markdown file in a directory as a lookup holding a single entry.
+    ///
+    /// Each file can start with YAML front matter between `---` lines, holding the
+    /// `name`, `description_short` and `category` of the entry. The rest of the file is
+    /// the description. Entries without a name are named after their file.
+    fn read_markdown_dir(dir: &Path) -> Vec<(PathBuf, Result<Lookup>)> {
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+            Ok(files) => files
+                .filter_map(|f| f.ok().map(|f| f.path()))
+                .filter(|p| p.is_file() && p.extension().unwrap_or_default() == "md")
+                .collect(),
+            Err(_) => vec![],
+        };
+        paths.sort();
+
+        let category = file_category(dir);
+        paths
+            .into_iter()
+            .map(|path| {
+                let lookup = Self::read_markdown(&path).map(|entry| Lookup {
+                    entries: HashMap::from([(entry.name.to_lowercase(), Rc::new(entry))]),
+                    category: category.clone(),
+                    ..Default::default()
+                });
+                (path, lookup)
+            })
+            .collect()
+    }
+
+    /// Read and parse a single markdown lookup entry.
+    fn read_markdown(path: &Path) -> Result<LookupEntry> {
+        let text = std::fs::read_to_string(path).wrap_err_with(|| {
+            format!(
+                "failed to read lookup file '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })?;
+
+        let (mut entry, body) = match text
+            .strip_prefix("---")
+            .and_then(|rest| rest.split_once("\n---"))
+        {
+            Some((front, body)) => {
+                let entry: LookupEntry = serde_yaml::from_str(front).wrap_err_with(|| {
                     format!(
-                        "failed to parse lookup file '{}'",
+                        "failed to parse front matter of lookup file '{}'",
                         path.to_str().unwrap_or(&path.to_string_lossy())
                     )
-                })
-                .unwrap();
-                self.entries.extend(lookup.entries.into_iter());
-            });
+                })?;
+                // Skip the rest of the closing `---` line
+                (entry, body.split_once('\n').map_or("", |(_, body)| body))
+            }
+            None => (LookupEntry::default(), text.as_str()),
+        };
 
-        self.loaded = true;
-        Ok(())
+        if entry.name.is_empty() {
+            entry.name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+        }
+        entry.description = body.trim().to_owned();
+        Ok(entry)
+    }
+
+    /// Take the problems found while loading lookup files, such as files that
+    /// could not be parsed and entries defined more than once.
+    pub fn take_diagnostics(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Rebuild the full-text index from the loaded entries.
+    fn build_index(&mut self) {
+        self.index.clear();
+        self.name_words = self
+            .entries
+            .keys()
+            .map(|k| Self::terms(k).len())
+            .max()
+            .unwrap_or(0);
+        for entry in self.entries.values() {
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            let text = [entry.name.clone(), entry.full_text()];
+            for word in text.iter().flat_map(|t| Self::terms(t)) {
+                *counts.entry(word).or_default() += 1;
+            }
+            for (word, count) in counts {
+                self.index
+                    .entry(word)
+                    .or_default()
+                    .push((entry.clone(), count));
+            }
+        }
+    }
+
+    /// Split text into the lowercase words used by the full-text index.
+    pub fn terms(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect()
+    }
+
+    /// Split text into the words to look up and any `key:value` filters, such as `level:3`.
+    pub fn filters(text: &str) -> (String, Vec<(String, String)>) {
+        let mut words = vec![];
+        let mut filters = vec![];
+        for word in text.split_whitespace() {
+            match word.split_once(':') {
+                Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+                    filters.push((key.to_lowercase(), value.to_lowercase()))
+                }
+                _ => words.push(word),
+            }
+        }
+        (words.join(" "), filters)
     }
 
     /// Get the lookup entry with the current name, if it exists.
@@ -105,21 +604,262 @@ impl Lookup {
         Ok(self.entries.get(&text))
     }
 
-    /// Search the lookup table for all possible completions for the given text
+    /// Find the names of entries mentioned in a line of text.
+    ///
+    /// Returns the byte range and name of each mention, preferring the longest name
+    /// starting at each word.
+    pub fn find_references(&mut self, line: &str) -> Result<Vec<(Range<usize>, String)>> {
+        if !self.loaded {
+            self.load()?;
+        }
+
+        // ASCII lowercase keeps byte offsets the same as the original line
+        let lower = line.to_ascii_lowercase();
+        let words: Vec<Range<usize>> = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| {
+                let start = w.as_ptr() as usize - lower.as_ptr() as usize;
+                start..start + w.len()
+            })
+            .collect();
+
+        let mut references = vec![];
+        let mut i = 0;
+        while i < words.len() {
+            let longest = self.name_words.min(words.len() - i);
+            let found = (1..=longest).rev().find_map(|n| {
+                let range = words[i].start..words[i + n - 1].end;
+                self.entries
+                    .contains_key(&lower[range.clone()])
+                    .then_some((n, range))
+            });
+
+            match found {
+                Some((n, range)) => {
+                    references.push((range.clone(), lower[range].to_owned()));
+                    i += n;
+                }
+                None => i += 1,
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Get every category of the loaded entries, in alphabetical order.
+    pub fn categories(&mut self) -> Result<Vec<String>> {
+        if !self.loaded {
+            self.load()?;
+        }
+        let mut categories: Vec<String> =
+            self.entries.values().map(|e| e.category.clone()).collect();
+        categories.sort();
+        categories.dedup();
+        Ok(categories)
+    }
+
+    /// Search the lookup table for all possible completions for the given text.
+    ///
+    /// Names are matched fuzzily, so substrings and small typos still match. Results are
+    /// grouped by category, with the group holding the best match first. Within a group,
+    /// names starting with the text come first in alphabetical order, followed by the
+    /// looser matches from best to worst.
     pub fn get_completions(&mut self, text: &str) -> Result<Vec<Rc<LookupEntry>>> {
         if !self.loaded {
             self.load()?;
         }
         let text = text.trim_start().to_lowercase();
-        Ok(self
+        let matches: Vec<(u32, &Rc<LookupEntry>)> = self
             .entries
             .iter()
-            .filter_map(|(k, v)| {
-                if k.starts_with(&text) {
-                    return Some(v.clone());
-                }
-                None
+            .filter_map(|(k, v)| fuzzy_score(&text, k).map(|score| (score, v)))
+            .collect();
+
+        let mut best: HashMap<&str, u32> = HashMap::new();
+        for (score, entry) in matches.iter() {
+            let group = best.entry(entry.category.as_str()).or_default();
+            *group = (*group).max(*score);
+        }
+
+        let mut matches: Vec<_> = matches
+            .iter()
+            .map(|(score, entry)| {
+                let prefix = *score >= PREFIX_SCORE;
+                let key = (
+                    Reverse(best[entry.category.as_str()]),
+                    entry.category.as_str(),
+                    Reverse(prefix),
+                    Reverse(if prefix { 0 } else { *score }),
+                    entry.name.as_str(),
+                );
+                (key, *entry)
             })
-            .collect())
+            .collect();
+
+        matches.sort_by_key(|(key, _)| *key);
+        Ok(matches.into_iter().map(|(_, v)| v.clone()).collect())
+    }
+
+    /// Search the names and descriptions of every entry for all of the words in the given text.
+    ///
+    /// The last word may be incomplete and matches any word it starts. Results are ranked by
+    /// how often the words appear.
+    pub fn search_descriptions(&mut self, text: &str) -> Result<Vec<Rc<LookupEntry>>> {
+        if !self.loaded {
+            self.load()?;
+        }
+
+        let terms = Self::terms(text);
+        let mut results: Option<HashMap<*const LookupEntry, (Rc<LookupEntry>, u32)>> = None;
+        for (i, term) in terms.iter().enumerate() {
+            let mut found: HashMap<*const LookupEntry, (Rc<LookupEntry>, u32)> = HashMap::new();
+            let postings: Vec<&Vec<(Rc<LookupEntry>, u32)>> = if i == terms.len() - 1 {
+                self.index
+                    .iter()
+                    .filter(|(word, _)| word.starts_with(term.as_str()))
+                    .map(|(_, postings)| postings)
+                    .collect()
+            } else {
+                self.index.get(term).into_iter().collect()
+            };
+
+            for (entry, count) in postings.into_iter().flatten() {
+                found
+                    .entry(Rc::as_ptr(entry))
+                    .or_insert_with(|| (entry.clone(), 0))
+                    .1 += count;
+            }
+
+            results = Some(match results {
+                None => found,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(ptr, (entry, count))| {
+                        found
+                            .get(&ptr)
+                            .map(|(_, more)| (ptr, (entry, count + more)))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut matches: Vec<(Rc<LookupEntry>, u32)> =
+            results.unwrap_or_default().into_values().collect();
+        matches.sort_by(|(a, a_count), (b, b_count)| {
+            b_count.cmp(a_count).then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(matches.into_iter().map(|(entry, _)| entry).collect())
+    }
+}
+
+/// Get the default category for the entries of a lookup file from its name.
+pub(crate) fn file_category(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    if let Some(stem) = stem.strip_suffix("sses") {
+        format!("{}ss", stem)
+    } else {
+        stem.strip_suffix('s').unwrap_or(&stem).to_owned()
+    }
+}
+
+/// The lowest score given to keys that start with the search text.
+const PREFIX_SCORE: u32 = 3001;
+
+/// Score how well a lowercase lookup key matches the search text, if it matches at all.
+///
+/// Higher scores are better matches.
+fn fuzzy_score(text: &str, key: &str) -> Option<u32> {
+    if key.starts_with(text) {
+        // Shorter names are closer to what was typed
+        return Some(4000 - key.len().min(999) as u32);
     }
+
+    if let Some(pos) = key.find(text) {
+        let word_start = key[..pos].ends_with(|c: char| !c.is_alphanumeric());
+        let base = if word_start { 3000 } else { 2000 };
+        return Some(base - pos.min(999) as u32);
+    }
+
+    if let Some(gaps) = subsequence_gaps(text, key) {
+        return Some(1000 + 999 - gaps.min(999));
+    }
+
+    // Allow one typo for every four characters typed
+    let max_typos = text.chars().count() / 4;
+    if max_typos > 0 {
+        let typos = prefix_distance(text, key);
+        if typos <= max_typos {
+            return Some(500 - typos.min(5) as u32 * 100);
+        }
+    }
+
+    None
+}
+
+/// Count the characters skipped when matching `text` as an in-order subsequence of `key`.
+///
+/// The first character must start a word in `key`.
+fn subsequence_gaps(text: &str, key: &str) -> Option<u32> {
+    let first = text.chars().next()?;
+    let mut best = None;
+    let mut prev = ' ';
+    for (start, c) in key.char_indices() {
+        let word_start = !prev.is_alphanumeric();
+        prev = c;
+        if c != first || !word_start {
+            continue;
+        }
+
+        let mut gaps = 0;
+        let mut key_chars = key[start..].chars();
+        let matched = text.chars().all(|t| {
+            for k in key_chars.by_ref() {
+                if k == t {
+                    return true;
+                }
+                gaps += 1;
+            }
+            false
+        });
+
+        if matched && best.is_none_or(|b| gaps < b) {
+            best = Some(gaps);
+        }
+    }
+    best
+}
+
+/// Get the smallest edit distance between `text` and any prefix of `key`.
+///
+/// Adjacent transpositions count as a single edit.
+fn prefix_distance(text: &str, key: &str) -> usize {
+    let text: Vec<char> = text.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+
+    // rows[i][j] is the distance between the first i characters of text and the first j of key
+    let mut rows = vec![vec![0; key.len() + 1]; text.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=key.len()).collect();
+
+    for i in 1..=text.len() {
+        for j in 1..=key.len() {
+            let cost = usize::from(text[i - 1] != key[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && text[i - 1] == key[j - 2] && text[i - 2] == key[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[text.len()].iter().copied().min().unwrap_or(text.len())
 }
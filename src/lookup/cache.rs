@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::SystemTime,
+};
+
+use color_eyre::eyre::{Result, WrapErr};
+use serde_derive::{Deserialize, Serialize};
+
+use super::{Lookup, LookupEntry};
+use crate::metadata::Metadata;
+
+/// The version of the cache format, changed whenever cached data would no longer load.
+const CACHE_VERSION: u32 = 3;
+
+/// The path, size and modification time of every file a cache was built from.
+pub(super) type Fingerprint = Vec<(PathBuf, u64, SystemTime)>;
+
+/// Loaded lookup entries and their full-text index, stored so later sessions can skip parsing.
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    version: u32,
+    files: Fingerprint,
+    entries: Vec<(String, CachedEntry)>,
+    /// Each indexed word, with the position of each entry containing it in `entries`.
+    index: Vec<(String, Vec<(u32, u32)>)>,
+    name_words: usize,
+    diagnostics: Vec<String>,
+}
+
+/// A lookup entry in a form that can be stored without field names.
+#[derive(Serialize, Deserialize)]
+struct CachedEntry {
+    name: String,
+    description_short: String,
+    description: String,
+    category: String,
+    source: String,
+    homebrew: bool,
+    metadata: Option<Metadata>,
+}
+
+impl From<&LookupEntry> for CachedEntry {
+    fn from(entry: &LookupEntry) -> Self {
+        Self {
+            name: entry.name.clone(),
+            description_short: entry.description_short.clone(),
+            description: entry.description.clone(),
+            category: entry.category.clone(),
+            source: entry.source.clone(),
+            homebrew: entry.homebrew,
+            metadata: entry.metadata.clone(),
+        }
+    }
+}
+
+impl From<CachedEntry> for LookupEntry {
+    fn from(entry: CachedEntry) -> Self {
+        Self {
+            name: entry.name,
+            description_short: entry.description_short,
+            description: entry.description,
+            category: entry.category,
+            source: entry.source,
+            homebrew: entry.homebrew,
+            metadata: entry.metadata,
+        }
+    }
+}
+
+/// Get the fingerprint of the given lookup files, including the markdown files in any directories.
+pub(super) fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    let mut files = vec![];
+    for path in paths {
+        if path.is_dir() {
+            if let Ok(dir) = std::fs::read_dir(path) {
+                let mut markdown: Vec<PathBuf> = dir
+                    .filter_map(|f| f.ok().map(|f| f.path()))
+                    .filter(|p| p.is_file() && p.extension().unwrap_or_default() == "md")
+                    .collect();
+                markdown.sort();
+                files.extend(markdown);
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let meta = std::fs::metadata(&path).ok()?;
+            Some((path, meta.len(), meta.modified().ok()?))
+        })
+        .collect()
+}
+
+impl Lookup {
+    /// Load the entries and index from the cache file, if it was built from the same files.
+    ///
+    /// Returns whether the cache was used. Missing, outdated and unreadable caches are ignored.
+    pub(super) fn read_cache(&mut self, path: &Path, files: &Fingerprint) -> bool {
+        let Ok(data) = std::fs::read(path) else {
+            return false;
+        };
+        let cache: Cache = match bincode::deserialize(&data) {
+            Ok(cache) => cache,
+            Err(_) => return false,
+        };
+        if cache.version != CACHE_VERSION || &cache.files != files {
+            return false;
+        }
+
+        let entries: Vec<(String, Rc<LookupEntry>)> = cache
+            .entries
+            .into_iter()
+            .map(|(key, entry)| (key, Rc::new(entry.into())))
+            .collect();
+        self.index = cache
+            .index
+            .into_iter()
+            .map(|(word, postings)| {
+                let postings = postings
+                    .into_iter()
+                    .filter_map(|(i, count)| Some((entries.get(i as usize)?.1.clone(), count)))
+                    .collect();
+                (word, postings)
+            })
+            .collect();
+        self.entries = entries.into_iter().collect();
+        self.name_words = cache.name_words;
+        self.diagnostics.extend(cache.diagnostics);
+        true
+    }
+
+    /// Store the loaded entries and index in the cache file.
+    ///
+    /// `diagnostics` are the problems found while loading, which are reported again
+    /// whenever the cache is used.
+    pub(super) fn write_cache(
+        &self,
+        path: &Path,
+        files: Fingerprint,
+        diagnostics: Vec<String>,
+    ) -> Result<()> {
+        let mut positions: HashMap<*const LookupEntry, u32> = HashMap::new();
+        let mut entries = vec![];
+        for (key, entry) in self.entries.iter() {
+            positions.insert(Rc::as_ptr(entry), entries.len() as u32);
+            entries.push((key.clone(), CachedEntry::from(entry.as_ref())));
+        }
+
+        let index = self
+            .index
+            .iter()
+            .map(|(word, postings)| {
+                let postings = postings
+                    .iter()
+                    .map(|(entry, count)| (positions[&Rc::as_ptr(entry)], *count))
+                    .collect();
+                (word.clone(), postings)
+            })
+            .collect();
+
+        let cache = Cache {
+            version: CACHE_VERSION,
+            files,
+            entries,
+            index,
+            name_words: self.name_words,
+            diagnostics,
+        };
+        let data = bincode::serialize(&cache)?;
+        std::fs::write(path, data).wrap_err_with(|| {
+            format!(
+                "failed to write lookup cache '{}'",
+                path.to_str().unwrap_or(&path.to_string_lossy())
+            )
+        })
+    }
+}
@@ -3,11 +3,12 @@ use std::path::PathBuf;
 
 use scrim::core::{settings::Settings, App};
 use scrim::event::{Event, EventHandler};
+use scrim::import::import;
 use scrim::lookup::Lookup;
 use scrim::tui::Tui;
 use scrim::update::update;
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 fn main() -> Result<()> {
@@ -30,12 +31,40 @@ fn main() -> Result<()> {
         lookup_path.push(".scrim/")
     }
 
+    // `scrim import [--force] <file> [output]` converts other tools' data into a lookup file
+    if path.is_some_and(|p| p == "import") {
+        let force = args[2..].iter().any(|a| a == "--force");
+        let args: Vec<_> = args[2..].iter().filter(|a| *a != "--force").collect();
+        let Some(input) = args.first().map(PathBuf::from) else {
+            return Err(eyre!("usage: scrim import [--force] <file> [output]"));
+        };
+        let output = match args.get(1) {
+            Some(output) => PathBuf::from(output),
+            None => {
+                std::fs::create_dir_all(&lookup_path)?;
+                lookup_path.join(input.with_extension("yaml").file_name().unwrap_or_default())
+            }
+        };
+        let (count, skipped) = import(&input, &output, force)?;
+        for skipped in skipped {
+            eprintln!("{}", skipped);
+        }
+        println!(
+            "imported {} entries into '{}'",
+            count,
+            output.to_string_lossy()
+        );
+        return Ok(());
+    }
+
     let mut settings_path = PathBuf::new();
     settings_path.push(home::home_dir().unwrap());
     settings_path.push(".scrim/");
     if !settings_path.as_path().exists() {
         std::fs::create_dir(settings_path.as_path())?;
     }
+    let cache_path = settings_path.join("lookups.cache");
+    let history_path = settings_path.join("lookups.history");
     settings_path.push("config.yaml");
     if !settings_path.exists() {
         println!("no settings found, autogenerating file...");
@@ -49,22 +78,51 @@ fn main() -> Result<()> {
         return Err(err);
     }
 
-    let mut lookup = Lookup::new(lookup_path);
+    let mut lookup = Lookup::new(lookup_path.clone()).with_cache(cache_path);
+    lookup.set_ignored_dirs(vec![app.settings().characters_dir()]);
 
     let backend = CrosstermBackend::new(std::io::stdout());
     let terminal = Terminal::new(backend)?;
     app.update_viewport_height(terminal.size()?.height);
     const AUTOSAVE_MINS: u64 = 5;
-    let events = EventHandler::new(AUTOSAVE_MINS * 60 * 1000);
+    let mut events = EventHandler::new(AUTOSAVE_MINS * 60 * 1000);
+
+    // Reload lookups and settings as they are edited. In release builds, lookups share the
+    // `.scrim` folder with the characters folder, whose saves and backups are not lookups.
+    let characters = app.settings().characters_dir();
+    let characters = std::fs::canonicalize(&characters).unwrap_or(characters);
+    let watched = events
+        .watch(
+            &lookup_path,
+            move |p| {
+                let player = p.starts_with(&characters)
+                    || p.components().any(|c| c.as_os_str() == "backups");
+                !player && (Lookup::is_lookup_file(p) || p.extension().unwrap_or_default() == "md")
+            },
+            Event::LookupsChanged,
+        )
+        .and_then(|_| {
+            events.watch(
+                settings_path.parent().unwrap(),
+                |p| p.file_name().unwrap_or_default() == "config.yaml",
+                Event::SettingsChanged,
+            )
+        });
+    if let Err(e) = watched {
+        app.show_error(format!("{:#}", e));
+    }
+    if let Err(e) = app.load_history(&history_path) {
+        app.show_error(format!("{:#}", e));
+    }
     let mut tui = Tui::new(terminal, events);
 
     // Load player data
-    if path.is_some() {
-        let path = PathBuf::from(path.as_ref().unwrap());
+    if let Some(path) = path {
+        let path = PathBuf::from(path);
         if path.exists() {
             match app.load_player(path) {
                 Ok(_) => {}
-                Err(e) => app.show_error(strip_ansi_escapes::strip_str(&format!("{:?}", e))),
+                Err(e) => app.show_error(strip_ansi_escapes::strip_str(format!("{:?}", e))),
             }
         }
     }
@@ -75,18 +133,34 @@ fn main() -> Result<()> {
         tui.draw(&mut app)?;
         // Handle events
         let res = match tui.events.next().unwrap() {
-            Event::Tick => app.save_player(),
-            Event::Key(key_event) => update(&mut app, &mut lookup, key_event),
+            Event::Tick => app.autosave(),
+            Event::Key(key_event) => {
+                let res = update(&mut app, &mut lookup, key_event);
+                app.check_unsaved_changes();
+                res
+            }
             Event::Mouse(_) => Ok(()),
             Event::Resize(_, y) => {
                 app.update_viewport_height(y);
                 Ok(())
             }
+            Event::LookupsChanged => lookup.reload(),
+            Event::SettingsChanged => app.reload_settings().map(|_| {
+                lookup.set_ignored_dirs(vec![app.settings().characters_dir()]);
+            }),
         };
 
         match res {
             Ok(_) => {}
-            Err(e) => app.show_error(strip_ansi_escapes::strip_str(&format!("{:?}", e))),
+            Err(e) => app.show_error(strip_ansi_escapes::strip_str(format!("{:?}", e))),
+        }
+
+        let diagnostics = lookup.take_diagnostics();
+        if !diagnostics.is_empty() {
+            app.show_error(format!(
+                "some lookups could not be loaded:\n\n{}",
+                diagnostics.join("\n\n")
+            ));
         }
     }
 
@@ -0,0 +1,344 @@
+use std::ops::Range;
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+/// A link to another lookup entry in rendered text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link {
+    /// The name of the linked entry.
+    pub target: String,
+    /// The line of the rendered text holding the link.
+    pub line: usize,
+    /// The byte range of the link text within the line.
+    pub range: Range<usize>,
+}
+
+/// A rendered span and the entry it links to, if any.
+type Tagged = (Span<'static>, Option<String>);
+
+/// Render lookup text written in a small subset of markdown into styled text.
+///
+/// Supported markup is `# headings`, `**bold**`, `*italics*` or `_italics_`,
+/// `- bullet` lists, simple `| pipe | tables |` and `[[links]]` to other entries,
+/// written as `[[name|shown text]]` to show different text. A `\` escapes the next
+/// character and markers that are never closed are shown as is, so plain text
+/// descriptions render unchanged.
+///
+/// Returns the styled text and every link in it, in reading order.
+pub fn render(text: &str) -> (Text<'static>, Vec<Link>) {
+    let mut lines = vec![];
+    let mut links = vec![];
+    let mut table: Vec<&str> = vec![];
+
+    for line in text.lines() {
+        if line.trim_start().starts_with('|') {
+            table.push(line.trim());
+            continue;
+        }
+
+        if !table.is_empty() {
+            for row in render_table(&table) {
+                lines.push(finish(row, lines.len(), &mut links));
+            }
+            table.clear();
+        }
+        lines.push(finish(render_line(line), lines.len(), &mut links));
+    }
+
+    if !table.is_empty() {
+        for row in render_table(&table) {
+            lines.push(finish(row, lines.len(), &mut links));
+        }
+    }
+
+    (Text::from(lines), links)
+}
+
+/// Get the text of a rendered line without any styling.
+pub fn plain(line: &Line) -> String {
+    line.spans.iter().map(|s| s.content.as_ref()).collect()
+}
+
+/// Get the number of rows a line of text takes up when word wrapped to the given width.
+///
+/// Words longer than the width are split across rows. A width of zero means the
+/// line is not wrapped.
+pub fn wrapped_rows(line: &str, width: u16) -> u32 {
+    let width = width as usize;
+    if width == 0 {
+        return 1;
+    }
+
+    let mut rows = 1;
+    let mut used = 0;
+    for word in line.split_inclusive(' ') {
+        let len = word.chars().count();
+        let mut word_len = word.trim_end_matches(' ').chars().count();
+        if used > 0 && used + word_len > width {
+            rows += 1;
+            used = 0;
+        }
+        while used + word_len > width {
+            rows += 1;
+            word_len -= width - used;
+            used = 0;
+        }
+        used += len;
+    }
+    rows
+}
+
+/// Patch the style of the given byte range of a rendered line, splitting spans as needed.
+pub fn style_range(line: Line<'static>, range: Range<usize>, style: Style) -> Line<'static> {
+    let mut spans = vec![];
+    let mut offset = 0;
+    for span in line.spans {
+        let content = span.content.as_ref();
+        let (start, end) = (offset, offset + content.len());
+        offset = end;
+        if range.end <= start || range.start >= end {
+            spans.push(span);
+            continue;
+        }
+
+        let from = range.start.max(start) - start;
+        let to = range.end.min(end) - start;
+        for (text, style) in [
+            (&content[..from], span.style),
+            (&content[from..to], span.style.patch(style)),
+            (&content[to..], span.style),
+        ] {
+            if !text.is_empty() {
+                spans.push(Span::styled(text.to_string(), style));
+            }
+        }
+    }
+    Line::from(spans)
+}
+
+/// Join tagged spans into a line, recording the position of every link.
+fn finish(spans: Vec<Tagged>, line: usize, links: &mut Vec<Link>) -> Line<'static> {
+    let mut offset = 0;
+    let spans = spans
+        .into_iter()
+        .map(|(span, target)| {
+            let len = span.content.len();
+            if let Some(target) = target {
+                links.push(Link {
+                    target,
+                    line,
+                    range: offset..offset + len,
+                });
+            }
+            offset += len;
+            span
+        })
+        .collect::<Vec<_>>();
+    Line::from(spans)
+}
+
+/// Render a single line of markup that is not part of a table.
+fn render_line(line: &str) -> Vec<Tagged> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level > 0 && trimmed[level..].starts_with(' ') {
+        let style = match level {
+            1 => Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            _ => Style::default().add_modifier(Modifier::BOLD),
+        };
+        let mut spans = inline(trimmed[level..].trim());
+        spans.iter_mut().for_each(|(s, _)| s.patch_style(style));
+        return spans;
+    }
+
+    if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        let mut spans = vec![(Span::raw(format!("{}  • ", indent)), None)];
+        spans.extend(inline(item));
+        return spans;
+    }
+
+    let mut spans = vec![(Span::raw(indent.to_string()), None)];
+    spans.extend(inline(trimmed));
+    spans
+}
+
+/// Render the rows of a pipe table with every column padded to the same width.
+///
+/// Rows above a `|---|---|` separator are treated as the header.
+fn render_table(rows: &[&str]) -> Vec<Vec<Tagged>> {
+    let rows: Vec<Vec<&str>> = rows
+        .iter()
+        .map(|row| {
+            let row = row.strip_prefix('|').unwrap_or(row);
+            let row = row.strip_suffix('|').unwrap_or(row);
+            row.split('|').map(str::trim).collect()
+        })
+        .collect();
+
+    let is_separator = |row: &Vec<&str>| {
+        row.iter().all(|cell| {
+            !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':') && cell.contains('-')
+        })
+    };
+    let header_rows = rows.iter().position(is_separator).unwrap_or(0);
+
+    let cells: Vec<Option<Vec<Vec<Tagged>>>> = rows
+        .iter()
+        .map(|row| {
+            if is_separator(row) {
+                None
+            } else {
+                Some(row.iter().map(|cell| inline(cell)).collect())
+            }
+        })
+        .collect();
+
+    let width = |cell: &Vec<Tagged>| -> usize { cell.iter().map(|(s, _)| s.width()).sum() };
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in cells.iter().flatten() {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(width(cell));
+        }
+    }
+
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let Some(row) = row else {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                return vec![(Span::raw(rule.join("─┼─")), None)];
+            };
+
+            let mut spans = vec![];
+            for (col, column_width) in widths.iter().enumerate() {
+                if col > 0 {
+                    spans.push((Span::raw(" │ "), None));
+                }
+
+                let cell = row.get(col).cloned().unwrap_or_default();
+                let len = width(&cell);
+                spans.extend(cell.into_iter().map(|(mut s, link)| {
+                    if i < header_rows {
+                        s.patch_style(Style::default().add_modifier(Modifier::BOLD));
+                    }
+                    (s, link)
+                }));
+                spans.push((Span::raw(" ".repeat(column_width - len)), None));
+            }
+            spans
+        })
+        .collect()
+}
+
+/// Split a line into spans at its bold, italic and link markers.
+fn inline(text: &str) -> Vec<Tagged> {
+    let mut spans = vec![];
+    let mut current = String::new();
+    let mut bold = false;
+    let mut italic = false;
+
+    let style = |bold: bool, italic: bool| {
+        let mut style = Style::default();
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        style
+    };
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let c = rest.chars().next().unwrap();
+
+        if c == '\\' {
+            if let Some(next) = rest[1..].chars().next() {
+                current.push(next);
+                i += 1 + next.len_utf8();
+                continue;
+            }
+        }
+
+        if let Some((link, _)) = rest.strip_prefix("[[").and_then(|r| r.split_once("]]")) {
+            let (target, shown) = link.split_once('|').unwrap_or((link, link));
+            if !current.is_empty() {
+                spans.push((
+                    Span::styled(std::mem::take(&mut current), style(bold, italic)),
+                    None,
+                ));
+            }
+            spans.push((
+                Span::styled(shown.trim().to_string(), style(bold, italic)),
+                Some(target.trim().to_string()),
+            ));
+            i += link.len() + 4;
+            continue;
+        }
+
+        let marker = if rest.starts_with("**") {
+            Some(("**", bold))
+        } else if c == '*' || (c == '_' && !italic && starts_word(text, i)) {
+            Some((&rest[..1], italic))
+        } else if c == '_' && italic && ends_word(text, i) {
+            Some(("_", italic))
+        } else {
+            None
+        };
+
+        // Only open a span if it will actually be closed later in the line
+        if let Some((marker, open)) = marker {
+            if open || rest[marker.len()..].contains(marker) {
+                if !current.is_empty() {
+                    spans.push((
+                        Span::styled(std::mem::take(&mut current), style(bold, italic)),
+                        None,
+                    ));
+                }
+                if marker == "**" {
+                    bold = !bold;
+                } else {
+                    italic = !italic;
+                }
+                i += marker.len();
+                continue;
+            }
+        }
+
+        current.push(c);
+        i += c.len_utf8();
+    }
+
+    if !current.is_empty() {
+        spans.push((Span::styled(current, style(bold, italic)), None));
+    }
+
+    spans
+}
+
+/// Whether the character at `i` is at the start of a word.
+fn starts_word(text: &str, i: usize) -> bool {
+    text[..i]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !c.is_alphanumeric())
+}
+
+/// Whether the character at `i` is at the end of a word.
+fn ends_word(text: &str, i: usize) -> bool {
+    text[i + 1..]
+        .chars()
+        .next()
+        .is_none_or(|c| !c.is_alphanumeric())
+}
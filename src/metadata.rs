@@ -0,0 +1,350 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Typed mechanics of a lookup entry, such as a spell's level or a weapon's damage.
+///
+/// In a lookup file this is a section named after the kind of entry, next to the
+/// usual entry fields:
+///
+/// ```yaml
+/// name: Fireball
+/// description: A bright streak flashes from your pointing finger...
+/// spell:
+///   level: 3
+///   school: Evocation
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Metadata {
+    Spell(SpellMetadata),
+    Weapon(WeaponMetadata),
+    Armor(ArmorMetadata),
+    Item(ItemMetadata),
+    Feat(FeatMetadata),
+    Race(RaceMetadata),
+    Class(ClassMetadata),
+    Monster(Box<MonsterMetadata>),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SpellMetadata {
+    /// The spell level, with 0 for cantrips.
+    pub level: u32,
+    pub school: String,
+    pub casting_time: String,
+    pub range: String,
+    pub components: String,
+    pub duration: String,
+    pub ritual: bool,
+    pub concentration: bool,
+    /// The classes with the spell on their spell list.
+    pub classes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct WeaponMetadata {
+    /// Whether the weapon is simple or martial, and possibly melee or ranged.
+    pub weapon_category: String,
+    /// The damage dice and type, as in `1d8 slashing`.
+    pub damage: String,
+    pub properties: Vec<String>,
+    /// The rarity of a magic weapon, or empty for mundane weapons.
+    pub rarity: String,
+    pub attunement: String,
+    pub cost: String,
+    pub weight: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ArmorMetadata {
+    /// Whether the armor is light, medium or heavy armor, or a shield.
+    pub armor_category: String,
+    /// The base armor class, or the bonus for shields.
+    pub ac: u32,
+    /// The strength score needed to wear the armor without being slowed, if any.
+    pub strength: u32,
+    pub stealth_disadvantage: bool,
+    /// The rarity of magic armor, or empty for mundane armor.
+    pub rarity: String,
+    pub attunement: String,
+    pub cost: String,
+    pub weight: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ItemMetadata {
+    pub rarity: String,
+    /// The attunement requirement, as in `requires attunement by a wizard`, or empty if none.
+    pub attunement: String,
+    /// The most charges the item can hold, or zero if it has none.
+    pub charges: u32,
+    /// How many charges the item regains, as in `1d6+1 at dawn` or `all at dawn`.
+    pub recharge: String,
+    pub cost: String,
+    pub weight: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct FeatMetadata {
+    pub prerequisite: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RaceMetadata {
+    pub size: String,
+    pub speed: String,
+    /// The ability score increases, as in `Strength +2, Charisma +1`.
+    pub ability: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ClassMetadata {
+    /// The number of faces on the class hit die.
+    pub hit_die: u32,
+    pub saving_throws: Vec<String>,
+    pub armor: String,
+    pub weapons: String,
+}
+
+/// The stat block of a monster or NPC.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct MonsterMetadata {
+    pub size: String,
+    /// The creature type, such as `beast` or `fiend (demon)`.
+    #[serde(rename = "type")]
+    pub creature_type: String,
+    pub alignment: String,
+    pub ac: u32,
+    pub hp: u32,
+    /// The hit dice the hit points are rolled with, as in `2d8 + 2`.
+    pub hit_dice: String,
+    /// Every speed of the creature, as in `30 ft., swim 30 ft.`.
+    pub speed: String,
+    pub str: u32,
+    pub dex: u32,
+    pub con: u32,
+    pub int: u32,
+    pub wis: u32,
+    pub cha: u32,
+    /// The saving throw bonuses, as in `Dex +4, Wis +2`.
+    pub saving_throws: String,
+    pub skills: String,
+    pub damage_resistances: String,
+    pub damage_immunities: String,
+    pub condition_immunities: String,
+    pub senses: String,
+    pub languages: String,
+    /// The challenge rating, as in `2` or `1/4`.
+    pub cr: String,
+    pub traits: Vec<MonsterFeature>,
+    pub actions: Vec<MonsterFeature>,
+    pub bonus_actions: Vec<MonsterFeature>,
+    pub reactions: Vec<MonsterFeature>,
+    pub legendary_actions: Vec<MonsterFeature>,
+}
+
+impl Default for MonsterMetadata {
+    fn default() -> Self {
+        Self {
+            size: String::new(),
+            creature_type: String::new(),
+            alignment: String::new(),
+            ac: 10,
+            hp: 1,
+            hit_dice: String::new(),
+            speed: String::new(),
+            str: 10,
+            dex: 10,
+            con: 10,
+            int: 10,
+            wis: 10,
+            cha: 10,
+            saving_throws: String::new(),
+            skills: String::new(),
+            damage_resistances: String::new(),
+            damage_immunities: String::new(),
+            condition_immunities: String::new(),
+            senses: String::new(),
+            languages: String::new(),
+            cr: String::new(),
+            traits: vec![],
+            actions: vec![],
+            bonus_actions: vec![],
+            reactions: vec![],
+            legendary_actions: vec![],
+        }
+    }
+}
+
+/// A named trait or action in a monster's stat block.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct MonsterFeature {
+    pub name: String,
+    pub description: String,
+}
+
+impl MonsterMetadata {
+    /// Get the ability scores, in the usual order from strength to charisma.
+    pub fn abilities(&self) -> [u32; 6] {
+        [self.str, self.dex, self.con, self.int, self.wis, self.cha]
+    }
+
+    /// Format the stat block as lookup markup, shown above the rest of the entry.
+    pub fn stat_block(&self) -> String {
+        let mut lines = vec![];
+        let kind = [&self.size, &self.creature_type]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        match (kind.is_empty(), self.alignment.is_empty()) {
+            (false, false) => lines.push(format!("*{}, {}*", kind, self.alignment)),
+            (false, true) => lines.push(format!("*{}*", kind)),
+            (true, false) => lines.push(format!("*{}*", self.alignment)),
+            (true, true) => {}
+        }
+        lines.push(String::new());
+
+        lines.push(format!("**Armor Class:** {}", self.ac));
+        if self.hit_dice.is_empty() {
+            lines.push(format!("**Hit Points:** {}", self.hp));
+        } else {
+            lines.push(format!("**Hit Points:** {} ({})", self.hp, self.hit_dice));
+        }
+        if !self.speed.is_empty() {
+            lines.push(format!("**Speed:** {}", self.speed));
+        }
+        lines.push(String::new());
+
+        lines.push(String::from("| STR | DEX | CON | INT | WIS | CHA |"));
+        lines.push(String::from("|---|---|---|---|---|---|"));
+        let scores: Vec<String> = self
+            .abilities()
+            .iter()
+            .map(|score| format!("{} ({:+})", score, *score as i32 / 2 - 5))
+            .collect();
+        lines.push(format!("| {} |", scores.join(" | ")));
+        lines.push(String::new());
+
+        for (label, value) in [
+            ("Saving Throws", &self.saving_throws),
+            ("Skills", &self.skills),
+            ("Damage Resistances", &self.damage_resistances),
+            ("Damage Immunities", &self.damage_immunities),
+            ("Condition Immunities", &self.condition_immunities),
+            ("Senses", &self.senses),
+            ("Languages", &self.languages),
+            ("Challenge", &self.cr),
+        ] {
+            if !value.is_empty() {
+                lines.push(format!("**{}:** {}", label, value));
+            }
+        }
+
+        let mut block = lines.join("\n");
+        for (heading, features) in [
+            ("", &self.traits),
+            ("Actions", &self.actions),
+            ("Bonus Actions", &self.bonus_actions),
+            ("Reactions", &self.reactions),
+            ("Legendary Actions", &self.legendary_actions),
+        ] {
+            if features.is_empty() {
+                continue;
+            }
+            if !heading.is_empty() {
+                block.push_str(&format!("\n\n## {}", heading));
+            }
+            for feature in features {
+                block.push_str(&format!(
+                    "\n\n***{}.*** {}",
+                    feature.name, feature.description
+                ));
+            }
+        }
+        block
+    }
+}
+
+impl Metadata {
+    /// Check whether a field of the metadata matches a `key:value` filter.
+    ///
+    /// Numbers, including text such as the `1/4` of a challenge rating, and flags must
+    /// match exactly, while other text only needs to contain the value. Values starting
+    /// with `<`, `<=`, `>` or `>=` compare numbers instead, so `cr:<=1/2` matches
+    /// challenge ratings of a half or less. Lists match if any of their items do.
+    /// Filters on fields the metadata does not have never match.
+    pub fn matches(&self, key: &str, value: &str) -> bool {
+        let Ok(Value::Object(kinds)) = serde_json::to_value(self) else {
+            return false;
+        };
+        let value = value.to_lowercase();
+        // Lists can be filtered by their singular name, as in `class:wizard`
+        let plurals = [format!("{}s", key), format!("{}es", key)];
+        kinds
+            .values()
+            .filter_map(|fields| {
+                fields
+                    .get(key)
+                    .or_else(|| plurals.iter().find_map(|k| fields.get(k)))
+            })
+            .any(|field| field_matches(field, &value))
+    }
+}
+
+/// Check whether a single metadata field matches a lowercase filter value.
+fn field_matches(field: &Value, value: &str) -> bool {
+    if let Some(matches) = compare(field, value) {
+        return matches;
+    }
+    match field {
+        Value::Number(n) => n.to_string() == value,
+        Value::Bool(b) => matches!((b, value), (true, "true" | "yes") | (false, "false" | "no")),
+        Value::String(s) => s.to_lowercase().contains(value),
+        Value::Array(items) => items.iter().any(|item| field_matches(item, value)),
+        _ => false,
+    }
+}
+
+/// Compare a numeric field with a filter value such as `2`, `<=2` or `>1/4`.
+///
+/// Returns `None` if either side is not a number.
+fn compare(field: &Value, value: &str) -> Option<bool> {
+    let (op, value) = ["<=", ">=", "<", ">"]
+        .into_iter()
+        .find_map(|op| Some((op, value.strip_prefix(op)?)))
+        .unwrap_or(("=", value));
+    let field = match field {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => number(s)?,
+        _ => return None,
+    };
+    let value = number(value)?;
+    Some(match op {
+        "<=" => field <= value,
+        ">=" => field >= value,
+        "<" => field < value,
+        ">" => field > value,
+        _ => field == value,
+    })
+}
+
+/// Parse a number, including fractions such as the `1/4` of a challenge rating.
+fn number(text: &str) -> Option<f64> {
+    match text.trim().split_once('/') {
+        Some((numerator, denominator)) => {
+            Some(numerator.trim().parse::<f64>().ok()? / denominator.trim().parse::<f64>().ok()?)
+        }
+        None => text.trim().parse().ok(),
+    }
+}
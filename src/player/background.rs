@@ -2,7 +2,11 @@ use num_derive::FromPrimitive;
 use serde_derive::{Deserialize, Serialize};
 use strum::{Display, EnumCount};
 
-#[derive(Clone, Copy, Default, FromPrimitive, Serialize, Deserialize, Display, EnumCount)]
+use super::{proficiencies::Proficiencies, ruleset::Ruleset, skills::*, stats::Stats};
+
+#[derive(
+    Clone, Copy, Default, FromPrimitive, Serialize, Deserialize, Display, EnumCount, PartialEq,
+)]
 pub enum Background {
     #[default]
     Acolyte,
@@ -22,16 +26,244 @@ pub enum Background {
     Sailor,
     Soldier,
     Urchin,
+    Artisan,
+    Farmer,
+    Guard,
+    Guide,
+    Merchant,
+    Scribe,
+    Wayfarer,
 }
 
+const BACKGROUNDS_2014: [Background; 15] = {
+    use Background::*;
+    [
+        Acolyte,
+        Charlatan,
+        Criminal,
+        Entertainer,
+        FolkHero,
+        GuildArtisan,
+        Hermit,
+        Knight,
+        Noble,
+        Outlander,
+        Pirate,
+        Sage,
+        Sailor,
+        Soldier,
+        Urchin,
+    ]
+};
+
+const BACKGROUNDS_2024: [Background; 16] = {
+    use Background::*;
+    [
+        Acolyte,
+        Artisan,
+        Charlatan,
+        Criminal,
+        Entertainer,
+        Farmer,
+        Guard,
+        Guide,
+        Hermit,
+        Merchant,
+        Noble,
+        Sage,
+        Sailor,
+        Scribe,
+        Soldier,
+        Wayfarer,
+    ]
+};
+
 impl Background {
-    pub fn cycle_next(&mut self) {
-        let next_val = std::cmp::min(*self as u8 + 1, Background::COUNT as u8 - 1);
-        *self = num_traits::FromPrimitive::from_u8(next_val).unwrap();
+    /// Get all backgrounds available under the given ruleset, in cycling order.
+    pub fn available(ruleset: Ruleset) -> &'static [Background] {
+        match ruleset {
+            Ruleset::Phb2014 => &BACKGROUNDS_2014,
+            Ruleset::Phb2024 => &BACKGROUNDS_2024,
+        }
+    }
+
+    /// Whether the background can be picked under the given ruleset.
+    pub fn is_available(&self, ruleset: Ruleset) -> bool {
+        Self::available(ruleset).contains(self)
+    }
+
+    /// Get the background that stands in for this one under the given ruleset, such as
+    /// the 2024 Artisan for a 2014 Guild Artisan.
+    ///
+    /// Backgrounds without a counterpart, such as the 2014 Folk Hero, are kept as they are.
+    pub fn for_ruleset(&self, ruleset: Ruleset) -> Self {
+        use Background::*;
+        match (self, ruleset) {
+            (GuildArtisan, Ruleset::Phb2024) => Artisan,
+            (Knight, Ruleset::Phb2024) => Noble,
+            (Pirate, Ruleset::Phb2024) => Sailor,
+            (Artisan, Ruleset::Phb2014) => GuildArtisan,
+            _ => *self,
+        }
+    }
+
+    /// Get the next background available under the given ruleset.
+    ///
+    /// A background that does not belong to the ruleset moves to the first available one.
+    pub fn get_next(&self, ruleset: Ruleset) -> Self {
+        let list = Self::available(ruleset);
+        match list.iter().position(|b| b == self) {
+            Some(i) => list[std::cmp::min(i + 1, list.len() - 1)],
+            None => list[0],
+        }
+    }
+
+    /// Get the previous background available under the given ruleset.
+    ///
+    /// A background that does not belong to the ruleset moves to the first available one.
+    pub fn get_prev(&self, ruleset: Ruleset) -> Self {
+        let list = Self::available(ruleset);
+        match list.iter().position(|b| b == self) {
+            Some(i) => list[i.saturating_sub(1)],
+            None => list[0],
+        }
     }
 
-    pub fn cycle_prev(&mut self) {
-        let prev_val = (*self as u8).saturating_sub(1);
-        *self = num_traits::FromPrimitive::from_u8(prev_val).unwrap();
+    /// Get the origin feat granted by the background at level 1, if any.
+    ///
+    /// Only 2024 backgrounds grant feats.
+    pub fn origin_feat(&self, ruleset: Ruleset) -> Option<&'static str> {
+        use Background::*;
+        if ruleset != Ruleset::Phb2024 {
+            return None;
+        }
+
+        match self {
+            Acolyte => Some("Magic Initiate (Cleric)"),
+            Artisan => Some("Crafter"),
+            Charlatan | Noble | Scribe => Some("Skilled"),
+            Criminal | Guard => Some("Alert"),
+            Entertainer => Some("Musician"),
+            Farmer => Some("Tough"),
+            Guide => Some("Magic Initiate (Druid)"),
+            Hermit => Some("Healer"),
+            Merchant | Wayfarer => Some("Lucky"),
+            Sage => Some("Magic Initiate (Wizard)"),
+            Sailor => Some("Tavern Brawler"),
+            Soldier => Some("Savage Attacker"),
+            _ => None,
+        }
+    }
+
+    /// Get the two skill proficiencies granted by the background.
+    pub fn skills(&self, ruleset: Ruleset) -> [usize; 2] {
+        use Background::*;
+        match (self, ruleset) {
+            (Acolyte, _) => [INSIGHT, RELIGION],
+            (Charlatan, _) => [DECEPTION, SLEIGHT_OF_HAND],
+            (Criminal, Ruleset::Phb2014) => [DECEPTION, STEALTH],
+            (Criminal, Ruleset::Phb2024) => [SLEIGHT_OF_HAND, STEALTH],
+            (Entertainer, _) => [ACROBATICS, PERFORMANCE],
+            (FolkHero, _) => [ANIMAL_HANDLING, SURVIVAL],
+            (GuildArtisan, _) => [INSIGHT, PERSUASION],
+            (Hermit, _) => [MEDICINE, RELIGION],
+            (Knight | Noble, _) => [HISTORY, PERSUASION],
+            (Outlander, _) => [ATHLETICS, SURVIVAL],
+            (Pirate, _) => [ATHLETICS, PERCEPTION],
+            (Sage, _) => [ARCANA, HISTORY],
+            (Sailor, Ruleset::Phb2014) => [ATHLETICS, PERCEPTION],
+            (Sailor, Ruleset::Phb2024) => [ACROBATICS, PERCEPTION],
+            (Soldier, _) => [ATHLETICS, INTIMIDATION],
+            (Urchin, _) => [SLEIGHT_OF_HAND, STEALTH],
+            (Artisan, _) => [INVESTIGATION, PERSUASION],
+            (Farmer, _) => [ANIMAL_HANDLING, NATURE],
+            (Guard, _) => [ATHLETICS, PERCEPTION],
+            (Guide, _) => [STEALTH, SURVIVAL],
+            (Merchant, _) => [ANIMAL_HANDLING, PERSUASION],
+            (Scribe, _) => [INVESTIGATION, PERCEPTION],
+            (Wayfarer, _) => [INSIGHT, STEALTH],
+        }
+    }
+
+    /// Get the languages and tool proficiencies granted by the background.
+    ///
+    /// Choices the player has to make are listed as placeholders that can be edited.
+    pub fn proficiencies(&self, ruleset: Ruleset) -> Proficiencies {
+        use Background::*;
+        const LANGUAGE: &str = "Language (choose one)";
+        const ARTISAN: &str = "Artisan's tools (choose one)";
+        const GAMING: &str = "Gaming set (choose one)";
+        const INSTRUMENT: &str = "Musical instrument (choose one)";
+
+        let (languages, tools): (&[&str], &[&str]) = match (self, ruleset) {
+            (Acolyte, Ruleset::Phb2014) => (&[LANGUAGE, LANGUAGE], &[]),
+            (Acolyte | Sage | Scribe, Ruleset::Phb2024) => (&[], &["Calligrapher's supplies"]),
+            (Charlatan, Ruleset::Phb2014) => (&[], &["Disguise kit", "Forgery kit"]),
+            (Charlatan, Ruleset::Phb2024) => (&[], &["Forgery kit"]),
+            (Criminal, Ruleset::Phb2014) => (&[], &[GAMING, "Thieves' tools"]),
+            (Criminal | Wayfarer, _) => (&[], &["Thieves' tools"]),
+            (Entertainer, Ruleset::Phb2014) => (&[], &["Disguise kit", INSTRUMENT]),
+            (Entertainer, Ruleset::Phb2024) => (&[], &[INSTRUMENT]),
+            (FolkHero, _) => (&[], &[ARTISAN, "Vehicles (land)"]),
+            (GuildArtisan, _) => (&[LANGUAGE], &[ARTISAN]),
+            (Hermit, Ruleset::Phb2014) => (&[LANGUAGE], &["Herbalism kit"]),
+            (Hermit, Ruleset::Phb2024) => (&[], &["Herbalism kit"]),
+            (Knight | Noble, Ruleset::Phb2014) => (&[LANGUAGE], &[GAMING]),
+            (Noble | Guard | Soldier, Ruleset::Phb2024) => (&[], &[GAMING]),
+            (Knight, _) => (&[LANGUAGE], &[GAMING]),
+            (Outlander, _) => (&[LANGUAGE], &[INSTRUMENT]),
+            (Pirate | Sailor, Ruleset::Phb2014) => {
+                (&[], &["Navigator's tools", "Vehicles (water)"])
+            }
+            (Sailor | Merchant, Ruleset::Phb2024) => (&[], &["Navigator's tools"]),
+            (Pirate | Merchant, _) => (&[], &["Navigator's tools", "Vehicles (water)"]),
+            (Sage, Ruleset::Phb2014) => (&[LANGUAGE, LANGUAGE], &[]),
+            (Soldier, Ruleset::Phb2014) => (&[], &[GAMING, "Vehicles (land)"]),
+            (Urchin, _) => (&[], &["Disguise kit", "Thieves' tools"]),
+            (Artisan, _) => (&[], &[ARTISAN]),
+            (Farmer, _) => (&[], &["Carpenter's tools"]),
+            (Guard | Scribe, _) => (&[], &[GAMING]),
+            (Guide, _) => (&[], &["Cartographer's tools"]),
+        };
+
+        Proficiencies::from_lists(languages, tools, &[], &[])
+    }
+
+    /// Get the ability score increases granted by the background.
+    ///
+    /// 2024 backgrounds let the player choose between +2/+1 and +1/+1/+1 among three abilities.
+    /// The even +1/+1/+1 spread is used here; stats can be adjusted by hand for the other option.
+    pub fn stats(&self, ruleset: Ruleset) -> Stats {
+        use Background::*;
+        if ruleset != Ruleset::Phb2024 {
+            return Stats::zero();
+        }
+
+        // Str, Dex, Con, Int, Wis, Cha
+        let abilities: [u32; 6] = match self {
+            Acolyte => [0, 0, 0, 1, 1, 1],
+            Artisan => [1, 1, 0, 1, 0, 0],
+            Charlatan => [0, 1, 1, 0, 0, 1],
+            Criminal => [0, 1, 1, 1, 0, 0],
+            Entertainer => [1, 1, 0, 0, 0, 1],
+            Farmer => [1, 0, 1, 0, 1, 0],
+            Guard => [1, 0, 0, 1, 1, 0],
+            Guide => [0, 1, 1, 0, 1, 0],
+            Hermit => [0, 0, 1, 0, 1, 1],
+            Merchant => [0, 0, 1, 1, 0, 1],
+            Noble => [1, 0, 0, 1, 0, 1],
+            Sage => [0, 0, 1, 1, 1, 0],
+            Sailor => [1, 1, 0, 0, 1, 0],
+            Scribe => [0, 1, 0, 1, 1, 0],
+            Soldier => [1, 1, 1, 0, 0, 0],
+            Wayfarer => [0, 1, 0, 0, 1, 1],
+            _ => [0; 6],
+        };
+
+        let mut stats = Stats::zero();
+        for (i, bonus) in abilities.into_iter().enumerate() {
+            stats[i] = bonus;
+        }
+        stats
     }
 }
@@ -2,6 +2,8 @@ use num_derive::FromPrimitive;
 use serde_derive::{Deserialize, Serialize};
 use strum::{Display, EnumCount};
 
+use super::{proficiencies::Proficiencies, ruleset::Ruleset, skills::*};
+
 #[derive(
     Clone, Copy, Default, FromPrimitive, Serialize, Deserialize, Display, EnumCount, PartialEq,
 )]
@@ -33,6 +35,308 @@ impl Class {
         }
     }
 
+    /// Get the number of class skills the player may choose and the list to choose them from.
+    pub fn skill_choices(&self, ruleset: Ruleset) -> (usize, &'static [usize]) {
+        use Class::*;
+        match self {
+            Artificer => (
+                2,
+                &[
+                    ARCANA,
+                    HISTORY,
+                    INVESTIGATION,
+                    MEDICINE,
+                    NATURE,
+                    PERCEPTION,
+                    SLEIGHT_OF_HAND,
+                ],
+            ),
+            Bard => (
+                3,
+                &[
+                    ATHLETICS,
+                    ACROBATICS,
+                    SLEIGHT_OF_HAND,
+                    STEALTH,
+                    ARCANA,
+                    HISTORY,
+                    INVESTIGATION,
+                    NATURE,
+                    RELIGION,
+                    ANIMAL_HANDLING,
+                    INSIGHT,
+                    MEDICINE,
+                    PERCEPTION,
+                    SURVIVAL,
+                    DECEPTION,
+                    INTIMIDATION,
+                    PERFORMANCE,
+                    PERSUASION,
+                ],
+            ),
+            Barbarian => (
+                2,
+                &[
+                    ANIMAL_HANDLING,
+                    ATHLETICS,
+                    INTIMIDATION,
+                    NATURE,
+                    PERCEPTION,
+                    SURVIVAL,
+                ],
+            ),
+            Cleric => (2, &[HISTORY, INSIGHT, MEDICINE, PERSUASION, RELIGION]),
+            Druid => (
+                2,
+                &[
+                    ARCANA,
+                    ANIMAL_HANDLING,
+                    INSIGHT,
+                    MEDICINE,
+                    NATURE,
+                    PERCEPTION,
+                    RELIGION,
+                    SURVIVAL,
+                ],
+            ),
+            // The 2024 fighter adds persuasion to the list
+            Fighter if ruleset == Ruleset::Phb2024 => (
+                2,
+                &[
+                    ACROBATICS,
+                    ANIMAL_HANDLING,
+                    ATHLETICS,
+                    HISTORY,
+                    INSIGHT,
+                    INTIMIDATION,
+                    PERCEPTION,
+                    PERSUASION,
+                    SURVIVAL,
+                ],
+            ),
+            Fighter => (
+                2,
+                &[
+                    ACROBATICS,
+                    ANIMAL_HANDLING,
+                    ATHLETICS,
+                    HISTORY,
+                    INSIGHT,
+                    INTIMIDATION,
+                    PERCEPTION,
+                    SURVIVAL,
+                ],
+            ),
+            Monk => (
+                2,
+                &[ACROBATICS, ATHLETICS, HISTORY, INSIGHT, RELIGION, STEALTH],
+            ),
+            Paladin => (
+                2,
+                &[
+                    ATHLETICS,
+                    INSIGHT,
+                    INTIMIDATION,
+                    MEDICINE,
+                    PERSUASION,
+                    RELIGION,
+                ],
+            ),
+            Ranger => (
+                3,
+                &[
+                    ANIMAL_HANDLING,
+                    ATHLETICS,
+                    INSIGHT,
+                    INVESTIGATION,
+                    NATURE,
+                    PERCEPTION,
+                    STEALTH,
+                    SURVIVAL,
+                ],
+            ),
+            Rogue => (
+                4,
+                &[
+                    ACROBATICS,
+                    ATHLETICS,
+                    DECEPTION,
+                    INSIGHT,
+                    INTIMIDATION,
+                    INVESTIGATION,
+                    PERCEPTION,
+                    PERFORMANCE,
+                    PERSUASION,
+                    SLEIGHT_OF_HAND,
+                    STEALTH,
+                ],
+            ),
+            Sorcerer => (
+                2,
+                &[
+                    ARCANA,
+                    DECEPTION,
+                    INSIGHT,
+                    INTIMIDATION,
+                    PERSUASION,
+                    RELIGION,
+                ],
+            ),
+            Warlock => (
+                2,
+                &[
+                    ARCANA,
+                    DECEPTION,
+                    HISTORY,
+                    INTIMIDATION,
+                    INVESTIGATION,
+                    NATURE,
+                    RELIGION,
+                ],
+            ),
+            Wizard => (
+                2,
+                &[ARCANA, HISTORY, INSIGHT, INVESTIGATION, MEDICINE, RELIGION],
+            ),
+        }
+    }
+
+    /// Get the tool, weapon and armor proficiencies granted by the class.
+    pub fn proficiencies(&self, ruleset: Ruleset) -> Proficiencies {
+        use Class::*;
+        const ALL_ARMOR: [&str; 4] = ["Light armor", "Medium armor", "Heavy armor", "Shields"];
+        const MARTIAL: [&str; 2] = ["Simple weapons", "Martial weapons"];
+        const CASTER_2014: [&str; 5] = [
+            "Daggers",
+            "Darts",
+            "Slings",
+            "Quarterstaffs",
+            "Light crossbows",
+        ];
+        const FINESSE_2014: [&str; 5] = [
+            "Simple weapons",
+            "Hand crossbows",
+            "Longswords",
+            "Rapiers",
+            "Shortswords",
+        ];
+        const FINESSE_2024: [&str; 2] = [
+            "Simple weapons",
+            "Martial weapons with the Finesse or Light property",
+        ];
+
+        let is_2024 = ruleset == Ruleset::Phb2024;
+        match self {
+            Artificer => Proficiencies::from_lists(
+                &[],
+                &[
+                    "Thieves' tools",
+                    "Tinker's tools",
+                    "Artisan's tools (choose one)",
+                ],
+                &["Simple weapons"],
+                &["Light armor", "Medium armor", "Shields"],
+            ),
+            Barbarian => Proficiencies::from_lists(
+                &[],
+                &[],
+                &MARTIAL,
+                &["Light armor", "Medium armor", "Shields"],
+            ),
+            Bard => Proficiencies::from_lists(
+                &[],
+                &["Musical instruments (choose three)"],
+                if is_2024 {
+                    &FINESSE_2024[..]
+                } else {
+                    &FINESSE_2014
+                },
+                &["Light armor"],
+            ),
+            Cleric | Ranger => Proficiencies::from_lists(
+                &[],
+                &[],
+                if *self == Ranger {
+                    &MARTIAL
+                } else {
+                    &MARTIAL[..1]
+                },
+                &["Light armor", "Medium armor", "Shields"],
+            ),
+            Druid if is_2024 => Proficiencies::from_lists(
+                &[],
+                &["Herbalism kit"],
+                &["Simple weapons"],
+                &["Light armor", "Shields"],
+            ),
+            Druid => Proficiencies::from_lists(
+                &[],
+                &["Herbalism kit"],
+                &[
+                    "Clubs",
+                    "Daggers",
+                    "Darts",
+                    "Javelins",
+                    "Maces",
+                    "Quarterstaffs",
+                    "Scimitars",
+                    "Sickles",
+                    "Slings",
+                    "Spears",
+                ],
+                &["Light armor", "Medium armor", "Shields"],
+            ),
+            Fighter | Paladin => Proficiencies::from_lists(&[], &[], &MARTIAL, &ALL_ARMOR),
+            Monk => Proficiencies::from_lists(
+                &[],
+                &["Artisan's tools or musical instrument (choose one)"],
+                if is_2024 {
+                    &["Simple weapons", "Martial weapons with the Light property"]
+                } else {
+                    &["Simple weapons", "Shortswords"]
+                },
+                &[],
+            ),
+            Rogue => Proficiencies::from_lists(
+                &[],
+                &["Thieves' tools"],
+                if is_2024 {
+                    &FINESSE_2024[..]
+                } else {
+                    &FINESSE_2014
+                },
+                &["Light armor"],
+            ),
+            Sorcerer | Wizard => Proficiencies::from_lists(
+                &[],
+                &[],
+                if is_2024 {
+                    &MARTIAL[..1]
+                } else {
+                    &CASTER_2014[..]
+                },
+                &[],
+            ),
+            Warlock => Proficiencies::from_lists(&[], &[], &["Simple weapons"], &["Light armor"]),
+        }
+    }
+
+    /// Get the number of skills the class may have expertise in at the given level.
+    pub fn expertise(&self, level: u32, ruleset: Ruleset) -> usize {
+        use Class::*;
+        match (self, ruleset) {
+            (Rogue, _) if level >= 6 => 4,
+            (Rogue, _) => 2,
+            (Bard, Ruleset::Phb2014) if level >= 10 => 4,
+            (Bard, Ruleset::Phb2014) if level >= 3 => 2,
+            (Bard, Ruleset::Phb2024) if level >= 9 => 4,
+            (Bard, Ruleset::Phb2024) if level >= 2 => 2,
+            (Ranger, Ruleset::Phb2024) if level >= 9 => 3,
+            (Ranger, Ruleset::Phb2024) if level >= 2 => 1,
+            _ => 0,
+        }
+    }
+
     pub fn cycle_next(&mut self) {
         let next_val = std::cmp::min(*self as u8 + 1, Class::COUNT as u8 - 1);
         *self = num_traits::FromPrimitive::from_u8(next_val).unwrap();
@@ -0,0 +1,108 @@
+use num_derive::FromPrimitive;
+use serde_derive::{Deserialize, Serialize};
+use strum::{Display, EnumCount};
+
+use super::stats::Stats;
+use crate::metadata::MonsterMetadata;
+
+/// What kind of creature a companion is.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    FromPrimitive,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumCount,
+    PartialEq,
+)]
+pub enum CompanionKind {
+    #[default]
+    Companion,
+    Familiar,
+    #[strum(to_string = "Wild Shape")]
+    WildShape,
+}
+
+impl CompanionKind {
+    pub fn cycle_next(&mut self) {
+        let next_val = std::cmp::min(*self as u8 + 1, CompanionKind::COUNT as u8 - 1);
+        *self = num_traits::FromPrimitive::from_u8(next_val).unwrap();
+    }
+
+    pub fn cycle_prev(&mut self) {
+        let prev_val = (*self as u8).saturating_sub(1);
+        *self = num_traits::FromPrimitive::from_u8(prev_val).unwrap();
+    }
+}
+
+/// A second creature tracked alongside the player, such as an animal companion,
+/// a familiar or a wild shape form.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Companion {
+    /// The companion's name.
+    pub name: String,
+    /// What kind of creature the companion is.
+    pub kind: CompanionKind,
+    /// The companion's current health.
+    pub hp: u32,
+    /// The companion's maximum health.
+    pub max_hp: u32,
+    /// The amount of temporary hit points the companion has.
+    pub temp_hp: u32,
+    /// The companion's armor class.
+    pub ac: u32,
+    /// The companion's stats.
+    pub stats: Stats,
+    /// The companion's attacks, one per line.
+    pub attacks: Vec<String>,
+}
+
+impl Default for Companion {
+    fn default() -> Self {
+        Self {
+            name: String::from("companion"),
+            kind: CompanionKind::default(),
+            hp: 1,
+            max_hp: 1,
+            temp_hp: 0,
+            ac: 10,
+            stats: Stats::default(),
+            attacks: vec![],
+        }
+    }
+}
+
+impl Companion {
+    /// Create a companion from a monster's stat block, such as a beast to wild shape into.
+    ///
+    /// Each of the monster's actions becomes an attack.
+    pub fn from_monster(name: &str, kind: CompanionKind, monster: &MonsterMetadata) -> Self {
+        let mut stats = Stats::default();
+        for (idx, score) in monster.abilities().into_iter().enumerate() {
+            stats[idx] = score;
+        }
+        let attacks = monster
+            .actions
+            .iter()
+            .map(|action| {
+                let description = action.description.split_whitespace().collect::<Vec<_>>();
+                format!("{}: {}", action.name, description.join(" "))
+            })
+            .collect();
+
+        Self {
+            name: name.to_owned(),
+            kind,
+            hp: monster.hp,
+            max_hp: monster.hp,
+            temp_hp: 0,
+            ac: monster.ac,
+            stats,
+            attacks,
+        }
+    }
+}
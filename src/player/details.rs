@@ -0,0 +1,468 @@
+use serde_derive::{Deserialize, Serialize};
+
+use super::background::Background;
+
+/// The names of each detail field, in the order used by `Details::nth()`.
+pub static DETAIL_NAMES: [&str; 11] = [
+    "Personality Traits",
+    "Ideals",
+    "Bonds",
+    "Flaws",
+    "Backstory",
+    "Age",
+    "Height",
+    "Weight",
+    "Eyes",
+    "Hair",
+    "Skin",
+];
+
+/// The player's personality and appearance.
+///
+/// Every field is multi-line text, stored one line per entry just like the other tabs.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Details {
+    pub personality_traits: Vec<String>,
+    pub ideals: Vec<String>,
+    pub bonds: Vec<String>,
+    pub flaws: Vec<String>,
+    pub backstory: Vec<String>,
+    pub age: Vec<String>,
+    pub height: Vec<String>,
+    pub weight: Vec<String>,
+    pub eyes: Vec<String>,
+    pub hair: Vec<String>,
+    pub skin: Vec<String>,
+}
+
+impl Details {
+    pub fn nth(&self, idx: usize) -> &Vec<String> {
+        match idx {
+            0 => &self.personality_traits,
+            1 => &self.ideals,
+            2 => &self.bonds,
+            3 => &self.flaws,
+            4 => &self.backstory,
+            5 => &self.age,
+            6 => &self.height,
+            7 => &self.weight,
+            8 => &self.eyes,
+            9 => &self.hair,
+            10 => &self.skin,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn nth_mut(&mut self, idx: usize) -> &mut Vec<String> {
+        match idx {
+            0 => &mut self.personality_traits,
+            1 => &mut self.ideals,
+            2 => &mut self.bonds,
+            3 => &mut self.flaws,
+            4 => &mut self.backstory,
+            5 => &mut self.age,
+            6 => &mut self.height,
+            7 => &mut self.weight,
+            8 => &mut self.eyes,
+            9 => &mut self.hair,
+            10 => &mut self.skin,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Suggested personality traits, ideals, bonds and flaws for a background.
+struct SuggestionTable {
+    traits: [&'static str; 4],
+    ideals: [&'static str; 4],
+    bonds: [&'static str; 4],
+    flaws: [&'static str; 4],
+}
+
+static ACOLYTE: SuggestionTable = SuggestionTable {
+    traits: [
+        "I quote scripture for every occasion, whether it fits or not.",
+        "I am patient with everyone, since every soul can be saved.",
+        "I see omens in the smallest events.",
+        "I am uncomfortable anywhere without an altar nearby.",
+    ],
+    ideals: [
+        "Faith. My god guides my every step.",
+        "Charity. I help those in need, whatever it costs me.",
+        "Tradition. The old rites must be kept exactly as they were taught.",
+        "Aspiration. I will prove myself worthy of my god's favor.",
+    ],
+    bonds: [
+        "I would give my life to recover a lost relic of my faith.",
+        "The temple that raised me is my only true home.",
+        "I owe everything to the priest who took me in.",
+        "I seek revenge on those who desecrated my shrine.",
+    ],
+    flaws: [
+        "I judge others harshly for their lack of faith.",
+        "I trust anyone who claims to share my beliefs.",
+        "I am rigid in my thinking and hate to be proven wrong.",
+        "I hide a doubt about my god that eats at me.",
+    ],
+};
+
+static CHARLATAN: SuggestionTable = SuggestionTable {
+    traits: [
+        "I have a new name and story for every town I visit.",
+        "I flatter people shamelessly to get what I want.",
+        "I can't resist testing a good lie on a stranger.",
+        "I keep a lucky coin and flip it before every decision.",
+    ],
+    ideals: [
+        "Independence. Nobody tells me how to live.",
+        "Fairness. I only con those who can afford to lose.",
+        "Creativity. A good scheme is a work of art.",
+        "Friendship. My marks are strangers, my friends are family.",
+    ],
+    bonds: [
+        "I once cheated someone who didn't deserve it and I mean to make it right.",
+        "My old partner in crime is the only person I fully trust.",
+        "I am saving every coin to buy back my family's name.",
+        "A powerful noble has sworn to see me hang.",
+    ],
+    flaws: [
+        "I can't pass up a chance to make easy money.",
+        "I lie even when the truth would serve me better.",
+        "I run at the first sign of real danger.",
+        "I am convinced nobody can see through my tricks.",
+    ],
+};
+
+static CRIMINAL: SuggestionTable = SuggestionTable {
+    traits: [
+        "I always know where the nearest exit is.",
+        "I never show what I'm really thinking.",
+        "I count the valuables in every room I walk into.",
+        "I speak softly and rarely use more words than needed.",
+    ],
+    ideals: [
+        "Honor. I never steal from my own.",
+        "Greed. Everything has a price, and I intend to collect it.",
+        "Freedom. Chains are meant to be broken.",
+        "Loyalty. My crew comes before any law.",
+    ],
+    bonds: [
+        "I am trying to pay off a debt to a dangerous guild.",
+        "Someone I love was jailed for a crime I committed.",
+        "My old gang betrayed me and I want what is mine.",
+        "I keep a fence's ledger that could ruin half the city.",
+    ],
+    flaws: [
+        "When I see something valuable, I start planning how to take it.",
+        "I turn on allies if the payoff is big enough.",
+        "I assume everyone is trying to cheat me.",
+        "I panic when I'm surrounded by guards.",
+    ],
+};
+
+static ENTERTAINER: SuggestionTable = SuggestionTable {
+    traits: [
+        "Every conversation is a stage and I play to the crowd.",
+        "I know a song, story or joke for any situation.",
+        "I can't stand being ignored.",
+        "I change my look every time I arrive in a new place.",
+    ],
+    ideals: [
+        "Beauty. Art makes the world worth living in.",
+        "People. I perform to make others happy.",
+        "Fame. I want my name remembered for generations.",
+        "Honesty. My art tells the truth, even when it stings.",
+    ],
+    bonds: [
+        "My instrument was a gift from my first teacher.",
+        "A rival performer stole my best act and I want it back.",
+        "I perform to support the troupe that raised me.",
+        "I will do anything to win the favor of a certain patron.",
+    ],
+    flaws: [
+        "I will do almost anything for applause.",
+        "I can't resist a pretty face.",
+        "I hold a grudge against every critic I've ever had.",
+        "I spend my coin as quickly as I earn it.",
+    ],
+};
+
+static FOLK_HERO: SuggestionTable = SuggestionTable {
+    traits: [
+        "I judge people by their actions, not their words.",
+        "I stand up for anyone being bullied.",
+        "I still think of myself as a simple farmhand.",
+        "I have little patience for fine manners.",
+    ],
+    ideals: [
+        "Respect. Everyone deserves to be treated with dignity.",
+        "Fairness. Nobody is above the law.",
+        "Destiny. I was meant for something greater.",
+        "Sincerity. I am who I say I am.",
+    ],
+    bonds: [
+        "I protect the village that raised me.",
+        "I want to find the tyrant who burned my home.",
+        "I carry the tools of my old trade wherever I go.",
+        "The common folk look to me, and I won't let them down.",
+    ],
+    flaws: [
+        "I am too quick to rush into a fight for the little guy.",
+        "I am secretly convinced I'm a fraud.",
+        "I distrust anyone who was born rich.",
+        "I can't say no when someone asks for help.",
+    ],
+};
+
+static GUILD_ARTISAN: SuggestionTable = SuggestionTable {
+    traits: [
+        "I judge every object by how well it was made.",
+        "I haggle over everything, even when I don't need to.",
+        "I talk about my craft at length to anyone who listens.",
+        "I am always tinkering with something in my hands.",
+    ],
+    ideals: [
+        "Community. The guild takes care of its own.",
+        "Craft. Work worth doing is worth doing perfectly.",
+        "Wealth. I want to be rich enough to never worry again.",
+        "Progress. New techniques make the world better.",
+    ],
+    bonds: [
+        "My workshop is the most important place in the world to me.",
+        "I owe my guild a debt I can never fully repay.",
+        "I am searching for a legendary tool lost generations ago.",
+        "A rival artisan ruined my reputation and I will prove them wrong.",
+    ],
+    flaws: [
+        "I will do anything to get my hands on a rare material.",
+        "I am quick to assume others are cheating me on price.",
+        "I can't keep a secret about my work.",
+        "I look down on people who can't make anything.",
+    ],
+};
+
+static HERMIT: SuggestionTable = SuggestionTable {
+    traits: [
+        "I am comfortable with long silences.",
+        "I forget social customs after so long alone.",
+        "I talk to animals and plants as though they answer.",
+        "I ponder every question for far too long.",
+    ],
+    ideals: [
+        "Solitude. Truth is found away from the noise of others.",
+        "Knowledge. I seek to understand the nature of things.",
+        "Self-knowledge. Know yourself and you know the world.",
+        "Free thinking. Nobody should be told what to believe.",
+    ],
+    bonds: [
+        "I left my retreat to share a discovery with the world.",
+        "I am searching for the person who drove me into exile.",
+        "My old mentor is still out there, waiting for my return.",
+        "I keep a journal of my visions that nobody else may read.",
+    ],
+    flaws: [
+        "I harbor dark thoughts I picked up in isolation.",
+        "I am certain that my discovery must be kept secret at any cost.",
+        "I get lost in my own thoughts at the worst times.",
+        "I distrust crowds and cities.",
+    ],
+};
+
+static NOBLE: SuggestionTable = SuggestionTable {
+    traits: [
+        "My manners are impeccable, even in a gutter.",
+        "I expect others to step aside when I walk by.",
+        "I take great pains to look my best at all times.",
+        "I treat servants kindly, but never as equals.",
+    ],
+    ideals: [
+        "Responsibility. Those with power must protect those without.",
+        "Family. Blood is everything.",
+        "Power. I will restore my house to its rightful place.",
+        "Noble obligation. I must earn the privileges I was born with.",
+    ],
+    bonds: [
+        "I will face any challenge to earn my family's approval.",
+        "My house's alliance with another family must be kept at all costs.",
+        "I am in love with someone my family would never accept.",
+        "I seek the ancestral heirloom stolen from our vault.",
+    ],
+    flaws: [
+        "I secretly believe I am better than everyone else.",
+        "I hide a scandal that could ruin my family.",
+        "I take insults to my name far too seriously.",
+        "I don't know how to do anything for myself.",
+    ],
+};
+
+static OUTLANDER: SuggestionTable = SuggestionTable {
+    traits: [
+        "I feel far more at home in the wild than in any town.",
+        "I watch the sky and always know what weather is coming.",
+        "I have a habit of collecting trophies from my hunts.",
+        "I am blunt, and don't understand why people take offense.",
+    ],
+    ideals: [
+        "Nature. The natural world is more important than civilization.",
+        "Greater good. It is each person's duty to protect the tribe.",
+        "Might. The strong are meant to lead.",
+        "Change. Life is like the seasons, always turning.",
+    ],
+    bonds: [
+        "My tribe is scattered and I will bring them back together.",
+        "I protect a sacred grove that others would destroy.",
+        "I am haunted by a beast I failed to kill.",
+        "I am the last to know a path through the wilds.",
+    ],
+    flaws: [
+        "I am slow to trust anyone outside my tribe.",
+        "I drink too much in towns to drown out the noise.",
+        "I would rather fight than talk.",
+        "I have no patience for laws I don't understand.",
+    ],
+};
+
+static SAGE: SuggestionTable = SuggestionTable {
+    traits: [
+        "I use long words to sound smarter than I am.",
+        "I have read every book in the world's greatest libraries, or so I claim.",
+        "I am always eager to explain things, whether asked or not.",
+        "I lose track of time when I'm reading.",
+    ],
+    ideals: [
+        "Knowledge. The path to power is through learning.",
+        "Logic. Emotion must not cloud careful thought.",
+        "Beauty. What is beautiful points to what is true.",
+        "No limits. Nothing should fetter the pursuit of knowledge.",
+    ],
+    bonds: [
+        "I have an ancient text that holds terrible secrets.",
+        "I am working on a great work that will outlast me.",
+        "My old academy is my true home.",
+        "I seek the answer to a question that has plagued me for years.",
+    ],
+    flaws: [
+        "I am easily distracted by the promise of new information.",
+        "I overlook obvious solutions in favor of clever ones.",
+        "I speak without thinking, often insulting others.",
+        "I can't keep a secret if it is interesting enough.",
+    ],
+};
+
+static SAILOR: SuggestionTable = SuggestionTable {
+    traits: [
+        "I tie knots in anything I get my hands on.",
+        "I judge a town by the quality of its taverns.",
+        "Every tale I tell gets a little taller each time.",
+        "I get restless when I can't smell salt in the air.",
+    ],
+    ideals: [
+        "Crew. A ship only floats when everyone pulls their weight.",
+        "Freedom. The horizon belongs to nobody.",
+        "Command. One day I will captain my own ship.",
+        "Adventure. There is always another port to find.",
+    ],
+    bonds: [
+        "My old captain took me in when nobody else would.",
+        "I mean to find the wreck of the ship I first sailed on.",
+        "A sweetheart waits for me in a distant harbor.",
+        "I was marooned by a mutinous crew and I remember every face.",
+    ],
+    flaws: [
+        "I take orders from a captain without question.",
+        "I spend my wages on drink the moment I reach port.",
+        "I believe every sea shanty and sailor's omen.",
+        "I pick fights with anyone who insults my ship.",
+    ],
+};
+
+static SOLDIER: SuggestionTable = SuggestionTable {
+    traits: [
+        "I keep my gear in perfect order, every single day.",
+        "I wake from nightmares of the battles I fought.",
+        "I size up every stranger as a potential threat.",
+        "I laugh at danger, mostly to hide my nerves.",
+    ],
+    ideals: [
+        "Duty. I protect those who cannot protect themselves.",
+        "Order. A chain of command keeps people alive.",
+        "Conscience. I follow orders only when they are right.",
+        "Strength. The side with the strongest arm decides the day.",
+    ],
+    bonds: [
+        "My old company is the only family I have left.",
+        "I carry the banner of a unit that no longer exists.",
+        "I owe my life to a comrade who didn't make it home.",
+        "I hunt the commander who led us into a slaughter.",
+    ],
+    flaws: [
+        "A mistake I made in battle still haunts me.",
+        "I treat every argument like a war to be won.",
+        "I can't forgive an enemy, even after the war is over.",
+        "I refuse to admit that I might be wrong.",
+    ],
+};
+
+static URCHIN: SuggestionTable = SuggestionTable {
+    traits: [
+        "I always keep a little food tucked away for later.",
+        "I am curious about everything and everyone.",
+        "I never sleep deeply, and wake at the slightest sound.",
+        "I know every back alley and rooftop shortcut in town.",
+    ],
+    ideals: [
+        "Dignity. Nobody deserves to be treated like dirt.",
+        "Community. The people of the streets look after one another.",
+        "Change. The proud will fall and the poor will rise.",
+        "Survival. Whatever it takes to see tomorrow.",
+    ],
+    bonds: [
+        "The streets of my home city are mine to defend.",
+        "I send money to the children who still live where I grew up.",
+        "An older urchin kept me alive, and I still owe them.",
+        "I will make sure no child suffers the way I did.",
+    ],
+    flaws: [
+        "I flee the moment a fight turns against me.",
+        "A handful of gold can buy almost anything from me.",
+        "I don't trust anybody but myself.",
+        "I fight dirty and don't see anything wrong with it.",
+    ],
+};
+
+impl Background {
+    /// Get the suggestion table for the background.
+    ///
+    /// Backgrounds without their own table use the table of the closest 2014 background.
+    fn suggestions(&self) -> &'static SuggestionTable {
+        use Background::*;
+        match self {
+            Acolyte => &ACOLYTE,
+            Charlatan => &CHARLATAN,
+            Criminal => &CRIMINAL,
+            Entertainer => &ENTERTAINER,
+            FolkHero | Farmer => &FOLK_HERO,
+            GuildArtisan | Artisan | Merchant => &GUILD_ARTISAN,
+            Hermit => &HERMIT,
+            Knight | Noble => &NOBLE,
+            Outlander | Guide => &OUTLANDER,
+            Sage | Scribe => &SAGE,
+            Pirate | Sailor => &SAILOR,
+            Soldier | Guard => &SOLDIER,
+            Urchin | Wayfarer => &URCHIN,
+        }
+    }
+
+    /// Get the suggested entries for the given detail field, if the field has suggestions.
+    pub fn detail_suggestions(&self, field: usize) -> Option<&'static [&'static str]> {
+        let table = self.suggestions();
+        match field {
+            0 => Some(&table.traits),
+            1 => Some(&table.ideals),
+            2 => Some(&table.bonds),
+            3 => Some(&table.flaws),
+            _ => None,
+        }
+    }
+}
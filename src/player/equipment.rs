@@ -0,0 +1,134 @@
+/// A PHB weapon and the properties needed to calculate attacks with it.
+pub struct Weapon {
+    pub name: &'static str,
+    pub martial: bool,
+    pub ranged: bool,
+    pub finesse: bool,
+    pub light: bool,
+}
+
+/// The weight class of a piece of armor.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArmorKind {
+    Light,
+    Medium,
+    Heavy,
+    Shield,
+}
+
+impl ArmorKind {
+    /// The name used for the armor's category in proficiency lists.
+    pub fn category(&self) -> &'static str {
+        match self {
+            ArmorKind::Light => "Light armor",
+            ArmorKind::Medium => "Medium armor",
+            ArmorKind::Heavy => "Heavy armor",
+            ArmorKind::Shield => "Shields",
+        }
+    }
+}
+
+/// A PHB armor and the values needed to calculate AC with it.
+pub struct Armor {
+    pub name: &'static str,
+    pub base: u32,
+    pub kind: ArmorKind,
+}
+
+macro_rules! weapon {
+    ($name:literal, $martial:literal, $ranged:literal, $finesse:literal, $light:literal) => {
+        Weapon {
+            name: $name,
+            martial: $martial,
+            ranged: $ranged,
+            finesse: $finesse,
+            light: $light,
+        }
+    };
+}
+
+macro_rules! armor {
+    ($name:literal, $base:literal, $kind:ident) => {
+        Armor {
+            name: $name,
+            base: $base,
+            kind: ArmorKind::$kind,
+        }
+    };
+}
+
+//                         name, martial, ranged, finesse, light
+pub static WEAPONS: [Weapon; 37] = [
+    weapon!("Club", false, false, false, true),
+    weapon!("Dagger", false, false, true, true),
+    weapon!("Greatclub", false, false, false, false),
+    weapon!("Handaxe", false, false, false, true),
+    weapon!("Javelin", false, false, false, false),
+    weapon!("Light hammer", false, false, false, true),
+    weapon!("Mace", false, false, false, false),
+    weapon!("Quarterstaff", false, false, false, false),
+    weapon!("Sickle", false, false, false, true),
+    weapon!("Spear", false, false, false, false),
+    weapon!("Light crossbow", false, true, false, false),
+    weapon!("Dart", false, true, true, false),
+    weapon!("Shortbow", false, true, false, false),
+    weapon!("Sling", false, true, false, false),
+    weapon!("Battleaxe", true, false, false, false),
+    weapon!("Flail", true, false, false, false),
+    weapon!("Glaive", true, false, false, false),
+    weapon!("Greataxe", true, false, false, false),
+    weapon!("Greatsword", true, false, false, false),
+    weapon!("Halberd", true, false, false, false),
+    weapon!("Lance", true, false, false, false),
+    weapon!("Longsword", true, false, false, false),
+    weapon!("Maul", true, false, false, false),
+    weapon!("Morningstar", true, false, false, false),
+    weapon!("Pike", true, false, false, false),
+    weapon!("Rapier", true, false, true, false),
+    weapon!("Scimitar", true, false, true, true),
+    weapon!("Shortsword", true, false, true, true),
+    weapon!("Trident", true, false, false, false),
+    weapon!("War pick", true, false, false, false),
+    weapon!("Warhammer", true, false, false, false),
+    weapon!("Whip", true, false, true, false),
+    weapon!("Blowgun", true, true, false, false),
+    weapon!("Hand crossbow", true, true, false, true),
+    weapon!("Heavy crossbow", true, true, false, false),
+    weapon!("Longbow", true, true, false, false),
+    weapon!("Net", true, true, false, false),
+];
+
+pub static ARMOR: [Armor; 13] = [
+    armor!("Padded", 11, Light),
+    armor!("Leather", 11, Light),
+    armor!("Studded leather", 12, Light),
+    armor!("Hide", 12, Medium),
+    armor!("Chain shirt", 13, Medium),
+    armor!("Scale mail", 14, Medium),
+    armor!("Breastplate", 14, Medium),
+    armor!("Half plate", 15, Medium),
+    armor!("Ring mail", 14, Heavy),
+    armor!("Chain mail", 16, Heavy),
+    armor!("Splint", 17, Heavy),
+    armor!("Plate", 18, Heavy),
+    armor!("Shield", 2, Shield),
+];
+
+/// Find the item whose name is the longest prefix of the given inventory line.
+fn find<'a, T>(items: &'a [T], name: fn(&T) -> &'static str, line: &str) -> Option<&'a T> {
+    let line = line.trim().to_lowercase();
+    items
+        .iter()
+        .filter(|i| line.starts_with(&name(i).to_lowercase()))
+        .max_by_key(|i| name(i).len())
+}
+
+/// Find the weapon named at the start of an inventory line, if any.
+pub fn find_weapon(line: &str) -> Option<&'static Weapon> {
+    find(&WEAPONS, |w| w.name, line)
+}
+
+/// Find the armor named at the start of an inventory line, if any.
+pub fn find_armor(line: &str) -> Option<&'static Armor> {
+    find(&ARMOR, |a| a.name, line)
+}
@@ -0,0 +1,107 @@
+use color_eyre::{eyre::eyre, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use super::util::roll_dice;
+use crate::metadata::Metadata;
+
+/// The most magic items a player can be attuned to at once.
+pub const MAX_ATTUNED: usize = 3;
+
+/// A magic item in the player's inventory, with its attunement and charges.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct MagicItem {
+    /// The item's name, as written in the inventory.
+    pub name: String,
+    /// The item's rarity, such as `rare`.
+    pub rarity: String,
+    /// Whether the item must be attuned to before it can be used.
+    pub requires_attunement: bool,
+    /// Whether the player is attuned to the item.
+    pub attuned: bool,
+    /// The number of charges the item has left.
+    pub charges: u32,
+    /// The most charges the item can hold, or zero if it has none.
+    pub max_charges: u32,
+    /// How many charges the item regains, as in `1d6+1 at dawn` or `all at dawn`.
+    pub recharge: String,
+}
+
+impl MagicItem {
+    /// Create a magic item from the metadata of its lookup entry, starting with full charges.
+    ///
+    /// Returns `None` for mundane items, which have no rarity, attunement or charges.
+    pub fn from_metadata(name: &str, metadata: &Metadata) -> Option<Self> {
+        let (rarity, attunement, charges, recharge) = match metadata {
+            Metadata::Item(item) => (&item.rarity, &item.attunement, item.charges, &item.recharge),
+            Metadata::Weapon(weapon) => (&weapon.rarity, &weapon.attunement, 0, &String::new()),
+            Metadata::Armor(armor) => (&armor.rarity, &armor.attunement, 0, &String::new()),
+            _ => return None,
+        };
+        let mundane = matches!(rarity.to_lowercase().as_str(), "" | "none" | "mundane");
+        if mundane && attunement.is_empty() && charges == 0 {
+            return None;
+        }
+
+        Some(Self {
+            name: name.to_owned(),
+            rarity: rarity.clone(),
+            requires_attunement: !attunement.is_empty(),
+            attuned: false,
+            charges,
+            max_charges: charges,
+            recharge: recharge.clone(),
+        })
+    }
+
+    /// Get the number of charges the item regains, rolling the first dice expression in its
+    /// recharge rule, as in `1d6 + 1` of `regains 1d6 + 1 at dawn`.
+    ///
+    /// Returns `None` if the item does not recharge, or an error if the rule could not be read.
+    pub fn recharge_amount(&self) -> Result<Option<u32>> {
+        let rule = self.recharge.trim().to_lowercase();
+        if self.max_charges == 0 || rule.is_empty() {
+            return Ok(None);
+        }
+        if rule.starts_with("all") {
+            return Ok(Some(self.max_charges));
+        }
+
+        let words: Vec<&str> = rule
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '+'))
+            .collect();
+        let is_dice = |w: &str| {
+            !w.is_empty()
+                && w.chars()
+                    .all(|c| c.is_ascii_digit() || c == 'd' || c == '+')
+        };
+        for start in 0..words.len() {
+            // The longest run of words that reads as dice, so `1d6 + 1` is not cut short
+            let end = start + words[start..].iter().take_while(|w| is_dice(w)).count();
+            if let Some(amount) = (start + 1..=end)
+                .rev()
+                .find_map(|end| roll_dice(&words[start..end].concat()))
+            {
+                return Ok(Some(amount));
+            }
+        }
+        Err(eyre!(
+            "could not read recharge rule '{}' for {}",
+            self.recharge,
+            self.name
+        ))
+    }
+
+    /// Regain charges as described by the item's recharge rule.
+    ///
+    /// Returns the number of charges regained, or `None` if the item does not recharge.
+    pub fn recharge(&mut self) -> Result<Option<u32>> {
+        let Some(amount) = self.recharge_amount()? else {
+            return Ok(None);
+        };
+        let regained = amount.min(self.max_charges - self.charges.min(self.max_charges));
+        self.charges += regained;
+        Ok(Some(regained))
+    }
+}
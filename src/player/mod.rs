@@ -4,10 +4,22 @@ pub mod alignment;
 pub mod background;
 /// Module containing all classes
 pub mod class;
+/// Module for keeping track of companions, familiars and wild shape forms
+pub mod companion;
+/// Module for keeping track of player personality and appearance
+pub mod details;
+/// Module containing PHB weapon and armor tables
+pub mod equipment;
 /// Module for keeping track of player funds
 pub mod funds;
+/// Module for keeping track of languages and tool, weapon and armor proficiencies
+pub mod proficiencies;
+/// Module for keeping track of magic item attunement and charges
+pub mod magic_items;
 /// Module containing all PHB races
 pub mod race;
+/// Module containing the supported rules editions
+pub mod ruleset;
 /// Module for keeping track of player skill proficiencies
 pub mod skills;
 /// Module for keeping track of and generating player spell slots
@@ -22,12 +34,18 @@ use std::path::Path;
 use self::alignment::Alignment;
 use self::background::Background;
 use self::class::Class;
+use self::companion::{Companion, CompanionKind};
+use self::details::Details;
+use self::equipment::{find_armor, find_weapon, ArmorKind};
 use self::funds::Funds;
+use self::magic_items::{MagicItem, MAX_ATTUNED};
+use self::proficiencies::Proficiencies;
 use self::race::Race;
-use self::skills::ProficiencyLevel;
+use self::ruleset::Ruleset;
+use self::skills::{ProficiencyLevel, ProficiencySource, SKILL_NAMES};
 use self::spells::SpellSlots;
 use self::stats::Stats;
-use self::util::{calculate_hp, get_modifier};
+use self::util::{calculate_hp, get_modifier, roll};
 
 use color_eyre::{
     eyre::{eyre, WrapErr},
@@ -40,6 +58,11 @@ use serde_derive::{Deserialize, Serialize};
 pub struct Player {
     /// The player's name.
     pub name: String,
+    /// The rules edition the player is built with.
+    ///
+    /// This should be modified via `Player::update_ruleset()` to ensure
+    /// that race and background bonuses are properly updated.
+    pub ruleset: Ruleset,
     /// The player's class.
     ///
     /// Whenever this is changed, `Player::recalculate()`
@@ -51,6 +74,9 @@ pub struct Player {
     /// should be called to ensure that all calculated values are up to date.
     pub level: u32,
     /// The player's background.
+    ///
+    /// This should be modified via `Player::update_background()` to ensure
+    /// that caluclated stat values are properly updated.
     pub background: Background,
     /// The player's alignment.
     pub alignment: Alignment,
@@ -96,7 +122,34 @@ pub struct Player {
     /// The player's current funds,
     pub funds: Funds,
     /// The players skill modifiers
+    ///
+    /// These should be modified via `Player::set_skill()` and friends to ensure
+    /// that the proficiency sources stay in sync.
     pub skills: [ProficiencyLevel; 18],
+    /// Where each skill proficiency came from.
+    ///
+    /// Players saved before sources were tracked treat every proficiency as manual.
+    #[serde(default = "manual_sources")]
+    pub skill_sources: [ProficiencySource; 18],
+    /// The player's languages and tool, weapon and armor proficiencies.
+    ///
+    /// Players saved before these were tracked start with empty lists.
+    #[serde(default)]
+    pub proficiencies: Proficiencies,
+    /// The player's current level of exhaustion.
+    pub exhaustion: u32,
+    /// The player's personality traits, ideals, bonds, flaws, backstory and appearance.
+    pub details: Details,
+    /// The player's companions, familiars and wild shape forms.
+    pub companions: Vec<Companion>,
+    /// The index of the wild shape form the player is currently in, if any.
+    ///
+    /// While in a wild shape, the form's physical stats replace the player's own.
+    pub wild_shape: Option<usize>,
+    /// The names of lookup entries pinned to the player, such as rules they often need.
+    pub pinned: Vec<String>,
+    /// The magic items in the player's inventory, with their attunement and charges.
+    pub magic_items: Vec<MagicItem>,
 }
 
 impl Player {
@@ -109,7 +162,7 @@ impl Player {
         })?;
 
         let ext = path.extension().unwrap_or_default();
-        let player = match ext.to_str().unwrap_or(&ext.to_string_lossy()) {
+        let mut player: Player = match ext.to_str().unwrap_or(&ext.to_string_lossy()) {
             // Legacy .player files always use JSON format
             "json" | "player" => serde_json::from_slice(bytes.as_slice()).wrap_err_with(|| {
                 format!(
@@ -117,7 +170,7 @@ impl Player {
                     path.to_str().unwrap_or(&path.to_string_lossy())
                 )
             }),
-            "yaml" => serde_yaml::from_slice(bytes.as_slice()).wrap_err_with(|| {
+            "yaml" | "yml" => serde_yaml::from_slice(bytes.as_slice()).wrap_err_with(|| {
                 format!(
                     "failed to parse file '{}'",
                     path.to_str().unwrap_or(&path.to_string_lossy())
@@ -129,6 +182,7 @@ impl Player {
             ))),
         }?;
 
+        player.claim_background_skills();
         Ok(player)
     }
 
@@ -139,22 +193,514 @@ impl Player {
         self.hit_dice_remaining = std::cmp::min(self.level, self.hit_dice_remaining + 1);
         self.prof_bonus = (self.level as f32 / 4.0).ceil() as u32 + 1;
 
-        self.spell_slots = SpellSlots::from(self.level, &self.class);
+        self.spell_slots = SpellSlots::from(self.level, &self.class, self.ruleset);
         self.spell_slots_remaining = self.spell_slots.clone();
 
         self.update_hp();
     }
 
     pub fn update_race(&mut self, race: Race) {
-        let old_race = self.race;
+        self.stats -= self.bonus_stats();
+        let old = self.granted_proficiencies();
         self.race = race;
+        self.stats += self.bonus_stats();
+        self.proficiencies.swap(&old, &self.granted_proficiencies());
+
+        self.recalculate();
+    }
+
+    pub fn update_background(&mut self, background: Background) {
+        self.stats -= self.bonus_stats();
+        let old = self.granted_proficiencies();
+        self.background = background;
+        self.stats += self.bonus_stats();
+        self.proficiencies.swap(&old, &self.granted_proficiencies());
+
+        self.apply_background_skills();
+        self.recalculate();
+    }
 
-        self.stats -= old_race.stats();
-        self.stats += race.stats();
+    pub fn update_ruleset(&mut self, ruleset: Ruleset) {
+        self.stats -= self.bonus_stats();
+        let old = self.granted_proficiencies();
+        self.ruleset = ruleset;
+        self.race = self.race.for_ruleset(ruleset);
+        self.background = self.background.for_ruleset(ruleset);
+        self.stats += self.bonus_stats();
+        self.proficiencies.swap(&old, &self.granted_proficiencies());
 
+        self.apply_background_skills();
         self.recalculate();
     }
 
+    /// Change the player's class, dropping any skills chosen from the old class list.
+    pub fn update_class(&mut self, class: Class) {
+        if class != self.class {
+            self.clear_skills(ProficiencySource::Class);
+        }
+        let old = self.granted_proficiencies();
+        self.class = class;
+        self.proficiencies.swap(&old, &self.granted_proficiencies());
+
+        self.recalculate();
+    }
+
+    /// Get the languages and proficiencies granted by the player's race, class and background.
+    fn granted_proficiencies(&self) -> Proficiencies {
+        let mut granted = self.race.proficiencies(self.ruleset);
+        granted.merge(&self.class.proficiencies(self.ruleset));
+        granted.merge(&self.background.proficiencies(self.ruleset));
+        granted
+    }
+
+    /// Get the wild shape form the player is currently in, if any.
+    pub fn wild_shape_form(&self) -> Option<&Companion> {
+        self.wild_shape.and_then(|idx| self.companions.get(idx))
+    }
+
+    /// Enter the given wild shape form, or leave it if the player is already in it.
+    ///
+    /// Returns false if the companion is not a wild shape form.
+    pub fn toggle_wild_shape(&mut self, idx: usize) -> bool {
+        if self.companions[idx].kind != CompanionKind::WildShape {
+            return false;
+        }
+
+        self.wild_shape = match self.wild_shape {
+            Some(current) if current == idx => None,
+            _ => Some(idx),
+        };
+        true
+    }
+
+    /// Remove a companion, leaving wild shape if it was the current form.
+    pub fn remove_companion(&mut self, idx: usize) {
+        self.companions.remove(idx);
+        self.wild_shape = match self.wild_shape {
+            Some(current) if current == idx => None,
+            Some(current) if current > idx => Some(current - 1),
+            other => other,
+        };
+    }
+
+    /// Get the number of magic items the player is attuned to.
+    pub fn attuned_count(&self) -> usize {
+        self.magic_items.iter().filter(|i| i.attuned).count()
+    }
+
+    /// Attune to a magic item, or end the attunement if the player is already attuned.
+    ///
+    /// Returns a warning if this leaves the player attuned to more items than allowed.
+    pub fn toggle_attunement(&mut self, idx: usize) -> Option<String> {
+        let item = &mut self.magic_items[idx];
+        item.attuned = !item.attuned;
+        let attuned = self.attuned_count();
+        if attuned > MAX_ATTUNED && self.magic_items[idx].attuned {
+            return Some(format!(
+                "Attuned to {} items, but only {} are allowed",
+                attuned, MAX_ATTUNED
+            ));
+        }
+        None
+    }
+
+    /// Keep the tracked magic items in step with the inventory.
+    ///
+    /// Items no longer in the inventory are dropped, and `found` items that are not
+    /// tracked yet are added.
+    pub fn update_magic_items(&mut self, found: Vec<MagicItem>) {
+        let in_inventory = |name: &str| {
+            self.inventory
+                .iter()
+                .any(|line| line.trim().eq_ignore_ascii_case(name))
+        };
+        let mut items: Vec<MagicItem> = self
+            .magic_items
+            .iter()
+            .filter(|i| in_inventory(&i.name))
+            .cloned()
+            .collect();
+        for item in found {
+            if !items
+                .iter()
+                .any(|i| i.name.eq_ignore_ascii_case(&item.name))
+            {
+                items.push(item);
+            }
+        }
+        self.magic_items = items;
+    }
+
+    /// Take a long rest, regaining hit points, hit dice, spell slots and magic item charges.
+    ///
+    /// Returns a line describing each thing regained.
+    pub fn long_rest(&mut self) -> Vec<String> {
+        let mut report = vec![];
+        if self.hp < self.max_hp {
+            report.push(format!("Regained {} HP", self.max_hp - self.hp));
+        }
+        self.hp = self.max_hp;
+        self.temp_hp = 0;
+
+        let hit_dice = match self.ruleset {
+            Ruleset::Phb2014 => (self.level / 2).max(1),
+            Ruleset::Phb2024 => self.level,
+        };
+        let remaining = (self.hit_dice_remaining + hit_dice).min(self.level);
+        if remaining > self.hit_dice_remaining {
+            report.push(format!(
+                "Regained {} hit dice",
+                remaining - self.hit_dice_remaining
+            ));
+        }
+        self.hit_dice_remaining = remaining;
+
+        self.spell_slots_remaining = self.spell_slots.clone();
+        if self.exhaustion > 0 {
+            self.exhaustion -= 1;
+            report.push(String::from("Removed 1 level of exhaustion"));
+        }
+
+        for item in self.magic_items.iter_mut() {
+            match item.recharge() {
+                Ok(Some(regained)) if regained > 0 => report.push(format!(
+                    "{} regained {} charges ({}/{})",
+                    item.name, regained, item.charges, item.max_charges
+                )),
+                Ok(_) => {}
+                Err(e) => report.push(format!("Warning: {}", e)),
+            }
+        }
+        report
+    }
+
+    /// Get the player's stats, with strength, dexterity and constitution taken from
+    /// the current wild shape form, if any.
+    pub fn effective_stats(&self) -> Stats {
+        let mut stats = self.stats.clone();
+        if let Some(form) = self.wild_shape_form() {
+            for i in 0..3 {
+                stats[i] = form.stats[i];
+            }
+        }
+        stats
+    }
+
+    /// Get the attack bonus for the weapon named at the start of an inventory line.
+    ///
+    /// Returns the bonus and whether the player is proficient with the weapon, or `None`
+    /// if the line does not name a known weapon. Finesse weapons use the better of
+    /// strength and dexterity.
+    pub fn attack_bonus(&self, line: &str) -> Option<(i32, bool)> {
+        let weapon = find_weapon(line)?;
+        let stats = self.effective_stats();
+        let strength = get_modifier(stats.strength);
+        let dexterity = get_modifier(stats.dexterity);
+        let modifier = if weapon.finesse {
+            strength.max(dexterity)
+        } else if weapon.ranged {
+            dexterity
+        } else {
+            strength
+        };
+
+        let proficient = self.proficiencies.has_weapon(
+            weapon.name,
+            weapon.martial,
+            weapon.finesse,
+            weapon.light,
+        );
+        let bonus = if proficient {
+            self.prof_bonus as i32
+        } else {
+            0
+        };
+
+        Some((
+            modifier + bonus - self.ruleset.exhaustion_penalty(self.exhaustion) as i32,
+            proficient,
+        ))
+    }
+
+    /// Calculate the player's AC from the first armor and shield in the inventory.
+    ///
+    /// Barbarians and monks without armor use their Unarmored Defense, and the ability
+    /// scores of any wild shape form are used. Returns the AC along with a warning for
+    /// each piece of armor the player is not proficient with.
+    pub fn calculate_ac(&self) -> (u32, Vec<String>) {
+        let stats = self.effective_stats();
+        let dexterity = get_modifier(stats.dexterity);
+        let mut warnings = vec![];
+        let mut body = None;
+        let mut shield = None;
+
+        for armor in self.inventory.iter().filter_map(|line| find_armor(line)) {
+            match armor.kind {
+                ArmorKind::Shield if shield.is_none() => shield = Some(armor),
+                ArmorKind::Shield => {}
+                _ if body.is_none() => body = Some(armor),
+                _ => {}
+            }
+        }
+
+        let mut ac = match body {
+            Some(armor) => {
+                let dexterity = match armor.kind {
+                    ArmorKind::Light => dexterity,
+                    ArmorKind::Medium => dexterity.min(2),
+                    _ => 0,
+                };
+                armor.base.saturating_add_signed(dexterity)
+            }
+            None => {
+                let unarmored = match self.class {
+                    Class::Barbarian => get_modifier(stats.constitution),
+                    Class::Monk if shield.is_none() => get_modifier(stats.wisdom),
+                    _ => 0,
+                };
+                10u32.saturating_add_signed(dexterity + unarmored)
+            }
+        };
+
+        if let Some(shield) = shield {
+            ac += shield.base;
+        }
+
+        for armor in body.into_iter().chain(shield) {
+            if !self.proficiencies.has_armor(armor.kind) {
+                warnings.push(format!(
+                    "not proficient with {} ({}): disadvantage on strength and dexterity rolls, and no spellcasting",
+                    armor.name,
+                    armor.kind.category().to_lowercase()
+                ));
+            }
+        }
+
+        (ac, warnings)
+    }
+
+    /// Roll a tool check, adding the proficiency bonus and subtracting any exhaustion penalty.
+    ///
+    /// Returns the d20 roll, the exhaustion penalty and the total. The ability modifier is not included since it
+    /// depends on the check being made.
+    pub fn roll_tool_check(&self) -> (u32, u32, i32) {
+        let roll = roll(20);
+        let penalty = self.ruleset.exhaustion_penalty(self.exhaustion);
+        let total = roll as i32 + self.prof_bonus as i32 - penalty as i32;
+        (roll, penalty, total)
+    }
+
+    /// Reset every skill that came from the given source.
+    fn clear_skills(&mut self, source: ProficiencySource) {
+        for (level, src) in self.skills.iter_mut().zip(self.skill_sources.iter_mut()) {
+            if *src == source {
+                *level = ProficiencyLevel::None;
+                *src = ProficiencySource::Manual;
+            }
+        }
+    }
+
+    /// Replace the skills granted by the previous background with those of the current one.
+    ///
+    /// Skills the player is already proficient in are left alone; `Player::skill_issues()`
+    /// will report the overlap so that a replacement can be chosen.
+    fn apply_background_skills(&mut self) {
+        self.clear_skills(ProficiencySource::Background);
+        for skill in self.background.skills(self.ruleset) {
+            if self.skills[skill] == ProficiencyLevel::None {
+                self.skills[skill] = ProficiencyLevel::Normal;
+                self.skill_sources[skill] = ProficiencySource::Background;
+            }
+        }
+    }
+
+    /// Mark the background's skills as coming from the background.
+    ///
+    /// Players saved before sources were tracked list every skill as manual, which would
+    /// otherwise report each background skill as granted twice.
+    fn claim_background_skills(&mut self) {
+        for skill in self.background.skills(self.ruleset) {
+            if self.skills[skill] != ProficiencyLevel::None
+                && self.skill_sources[skill] == ProficiencySource::Manual
+            {
+                self.skill_sources[skill] = ProficiencySource::Background;
+            }
+        }
+    }
+
+    /// Set a skill's proficiency level by hand.
+    ///
+    /// Skills granted by the background stay granted by it, so raising one to expertise
+    /// is not reported as a second source.
+    pub fn set_skill(&mut self, skill: usize, level: ProficiencyLevel) {
+        let granted = self.background.skills(self.ruleset).contains(&skill);
+        self.skills[skill] = level;
+        self.skill_sources[skill] = if granted && level != ProficiencyLevel::None {
+            ProficiencySource::Background
+        } else {
+            ProficiencySource::Manual
+        };
+    }
+
+    /// Get the number of skills chosen from the class skill list.
+    pub fn class_skill_count(&self) -> usize {
+        self.skill_sources
+            .iter()
+            .filter(|s| **s == ProficiencySource::Class)
+            .count()
+    }
+
+    /// Choose or unchoose a skill from the class skill list.
+    ///
+    /// Returns whether the skill was changed. Skills that are not on the list, that
+    /// the player is already proficient in, or that would exceed the number of class
+    /// skills are not chosen.
+    pub fn toggle_class_skill(&mut self, skill: usize) -> bool {
+        if self.skill_sources[skill] == ProficiencySource::Class {
+            self.skills[skill] = ProficiencyLevel::None;
+            self.skill_sources[skill] = ProficiencySource::Manual;
+            return true;
+        }
+
+        let (count, choices) = self.class.skill_choices(self.ruleset);
+        if self.skills[skill] != ProficiencyLevel::None
+            || !choices.contains(&skill)
+            || self.class_skill_count() >= count
+        {
+            return false;
+        }
+
+        self.skills[skill] = ProficiencyLevel::Normal;
+        self.skill_sources[skill] = ProficiencySource::Class;
+        true
+    }
+
+    /// Give the player expertise in a skill they are proficient in.
+    ///
+    /// Returns whether expertise was given. Expertise is limited to the amount granted
+    /// by the player's class at their current level.
+    pub fn set_expertise(&mut self, skill: usize) -> bool {
+        let used = self
+            .skills
+            .iter()
+            .filter(|s| **s == ProficiencyLevel::Double)
+            .count();
+
+        if self.skills[skill] != ProficiencyLevel::Normal
+            || used >= self.class.expertise(self.level, self.ruleset)
+        {
+            return false;
+        }
+
+        // The source is kept so the skill still shows where the proficiency came from
+        self.skills[skill] = ProficiencyLevel::Double;
+        true
+    }
+
+    /// Check the player's skills against the rules as written.
+    ///
+    /// Returns a list of problems, each with the skill it applies to, if any.
+    pub fn skill_issues(&self) -> Vec<(Option<usize>, String)> {
+        let mut issues = vec![];
+        let (count, choices) = self.class.skill_choices(self.ruleset);
+
+        for skill in self.background.skills(self.ruleset) {
+            if self.skills[skill] == ProficiencyLevel::None {
+                issues.push((
+                    Some(skill),
+                    format!(
+                        "{} is granted by the {} background but is not proficient",
+                        SKILL_NAMES[skill], self.background
+                    ),
+                ));
+            } else if self.skill_sources[skill] != ProficiencySource::Background {
+                issues.push((
+                    Some(skill),
+                    format!(
+                        "{} is granted by the {} background and another source, choose a different skill",
+                        SKILL_NAMES[skill], self.background
+                    ),
+                ));
+            }
+        }
+
+        for (skill, source) in self.skill_sources.iter().enumerate() {
+            if *source == ProficiencySource::Class && !choices.contains(&skill) {
+                issues.push((
+                    Some(skill),
+                    format!("{} is not a {} class skill", SKILL_NAMES[skill], self.class),
+                ));
+            }
+        }
+
+        let chosen = self.class_skill_count();
+        if chosen > count {
+            issues.push((
+                None,
+                format!(
+                    "{} chooses {} class skills, but {} are chosen",
+                    self.class, count, chosen
+                ),
+            ));
+        } else if chosen < count {
+            issues.push((
+                None,
+                format!("{} class skills left to choose", count - chosen),
+            ));
+        }
+
+        let allowed = self.class.expertise(self.level, self.ruleset);
+        let expertise: Vec<usize> = (0..18)
+            .filter(|i| self.skills[*i] == ProficiencyLevel::Double)
+            .collect();
+        if expertise.len() > allowed {
+            let message = if allowed == 0 {
+                format!("a level {} {} has no expertise", self.level, self.class)
+            } else {
+                format!(
+                    "a level {} {} has expertise in {} skills, not {}",
+                    self.level,
+                    self.class,
+                    allowed,
+                    expertise.len()
+                )
+            };
+            issues.push((None, message));
+            for skill in expertise {
+                issues.push((
+                    Some(skill),
+                    format!(
+                        "{} has expertise beyond the class limit",
+                        SKILL_NAMES[skill]
+                    ),
+                ));
+            }
+        }
+
+        issues
+    }
+
+    /// Get the ability score increases granted by the player's race and background
+    /// under the current ruleset.
+    fn bonus_stats(&self) -> Stats {
+        &self.race.stats(self.ruleset) + self.background.stats(self.ruleset)
+    }
+
+    /// Get the feats the player gains at level 1 from their background and race.
+    pub fn origin_feats(&self) -> Vec<&'static str> {
+        let mut feats: Vec<&'static str> = self
+            .background
+            .origin_feat(self.ruleset)
+            .into_iter()
+            .collect();
+
+        if self.ruleset == Ruleset::Phb2024 && self.race == Race::Human {
+            feats.push("Versatile");
+        }
+
+        feats
+    }
+
     /// Recalculates the player's max health and adjusts current
     /// health accordingly.
     fn update_hp(&mut self) {
@@ -173,16 +719,18 @@ impl Player {
 
     /// Get the stat modifier corresponding to the given skill
     fn get_skill_modifier(&self, skill: u32) -> i32 {
+        let stats = self.effective_stats();
         let val = match skill {
-            0 => self.stats.strength,
-            1 | 2 | 3 => self.stats.dexterity,
-            4 | 5 | 6 | 7 | 8 => self.stats.intelligence,
-            9 | 10 | 11 | 12 | 13 => self.stats.wisdom,
-            14 | 15 | 16 | 17 => self.stats.charisma,
+            0 => stats.strength,
+            1..=3 => stats.dexterity,
+            4..=8 => stats.intelligence,
+            9..=13 => stats.wisdom,
+            14..=17 => stats.charisma,
             _ => panic!("skill index '{}' out of range", skill),
         };
 
         get_modifier(val) + self.skills[skill as usize].get_mod(self.prof_bonus) as i32
+            - self.ruleset.exhaustion_penalty(self.exhaustion) as i32
     }
 
     /// Get all of the player's skill modifier values
@@ -196,9 +744,13 @@ impl Player {
     }
 }
 
+fn manual_sources() -> [ProficiencySource; 18] {
+    [ProficiencySource::Manual; 18]
+}
+
 impl Default for Player {
     fn default() -> Self {
-        Self {
+        let mut player = Self {
             level: 1,
             hit_dice: 8,
             hit_dice_remaining: 1,
@@ -206,9 +758,10 @@ impl Default for Player {
             ac: 10,
             max_hp: 10,
             prof_bonus: 2,
-            spell_slots: SpellSlots::from(1, &Class::Fighter),
-            spell_slots_remaining: SpellSlots::from(1, &Class::Fighter),
+            spell_slots: SpellSlots::from(1, &Class::Fighter, Ruleset::Phb2014),
+            spell_slots_remaining: SpellSlots::from(1, &Class::Fighter, Ruleset::Phb2014),
             name: String::default(),
+            ruleset: Ruleset::default(),
             class: Class::default(),
             background: Background::default(),
             alignment: Alignment::default(),
@@ -220,6 +773,17 @@ impl Default for Player {
             temp_hp: 0,
             funds: Funds::default(),
             skills: [ProficiencyLevel::default(); 18],
-        }
+            skill_sources: manual_sources(),
+            proficiencies: Proficiencies::default(),
+            exhaustion: 0,
+            details: Details::default(),
+            companions: vec![],
+            wild_shape: None,
+            pinned: vec![],
+            magic_items: vec![],
+        };
+        player.apply_background_skills();
+        player.proficiencies = player.granted_proficiencies();
+        player
     }
 }
@@ -0,0 +1,124 @@
+use serde_derive::{Deserialize, Serialize};
+
+use super::equipment::ArmorKind;
+
+/// The names of each proficiency category, in the order used by `Proficiencies::nth()`.
+pub static CATEGORY_NAMES: [&str; 4] = ["Languages", "Tools", "Weapons", "Armor"];
+
+/// The index of the tool category.
+pub const TOOLS: u32 = 1;
+
+/// A player's languages and tool, weapon and armor proficiencies.
+///
+/// Weapon and armor entries are either whole categories (`Simple weapons`,
+/// `Light armor`, `Shields`, ...) or the names of specific weapons.
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Proficiencies {
+    pub languages: Vec<String>,
+    pub tools: Vec<String>,
+    pub weapons: Vec<String>,
+    pub armor: Vec<String>,
+}
+
+impl Proficiencies {
+    /// Build a set of proficiencies from static lists.
+    pub fn from_lists(
+        languages: &[&str],
+        tools: &[&str],
+        weapons: &[&str],
+        armor: &[&str],
+    ) -> Self {
+        let to_vec = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        Self {
+            languages: to_vec(languages),
+            tools: to_vec(tools),
+            weapons: to_vec(weapons),
+            armor: to_vec(armor),
+        }
+    }
+
+    pub fn nth(&self, idx: u32) -> &Vec<String> {
+        match idx {
+            0 => &self.languages,
+            1 => &self.tools,
+            2 => &self.weapons,
+            3 => &self.armor,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn nth_mut(&mut self, idx: u32) -> &mut Vec<String> {
+        match idx {
+            0 => &mut self.languages,
+            1 => &mut self.tools,
+            2 => &mut self.weapons,
+            3 => &mut self.armor,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Add every entry of `other` that is not already present.
+    ///
+    /// Placeholders for choices are always added, since each one is a separate choice.
+    pub fn merge(&mut self, other: &Proficiencies) {
+        for idx in 0..4 {
+            let list = self.nth_mut(idx);
+            for entry in other.nth(idx) {
+                if !list.contains(entry) || entry.contains("(choose") {
+                    list.push(entry.clone());
+                }
+            }
+        }
+    }
+
+    /// Replace the entries granted by `old` with those granted by `new`.
+    ///
+    /// Only entries that still match `old` exactly are removed, so anything the
+    /// user has edited or added by hand is kept.
+    pub fn swap(&mut self, old: &Proficiencies, new: &Proficiencies) {
+        for idx in 0..4 {
+            let list = self.nth_mut(idx);
+            for entry in old.nth(idx) {
+                if let Some(pos) = list.iter().position(|e| e == entry) {
+                    list.remove(pos);
+                }
+            }
+        }
+        self.merge(new);
+    }
+
+    /// Check whether any entry matches the given name, ignoring case and a trailing plural `s`.
+    fn has(list: &[String], name: &str) -> bool {
+        let name = name.to_lowercase();
+        list.iter().any(|entry| {
+            let entry = entry.trim().to_lowercase();
+            entry == name || entry.strip_suffix('s') == Some(name.as_str())
+        })
+    }
+
+    /// Check whether the player is proficient with the given kind of armor.
+    pub fn has_armor(&self, kind: ArmorKind) -> bool {
+        Self::has(&self.armor, kind.category())
+            || (kind != ArmorKind::Shield && Self::has(&self.armor, "all armor"))
+    }
+
+    /// Check whether the player is proficient with the given weapon.
+    pub fn has_weapon(&self, name: &str, martial: bool, finesse: bool, light: bool) -> bool {
+        if Self::has(&self.weapons, name) {
+            return true;
+        }
+
+        self.weapons.iter().any(|entry| {
+            let entry = entry.trim().to_lowercase();
+            if !martial {
+                return entry.starts_with("simple weapon");
+            }
+            // 2024 classes can be limited to martial weapons with certain properties
+            entry.starts_with("martial weapon")
+                && (!entry.contains("property")
+                    || (finesse && entry.contains("finesse"))
+                    || (light && entry.contains("light")))
+        })
+    }
+}
@@ -2,7 +2,7 @@ use num_derive::FromPrimitive;
 use serde_derive::{Deserialize, Serialize};
 use strum::{Display, EnumCount};
 
-use super::stats::Stats;
+use super::{proficiencies::Proficiencies, ruleset::Ruleset, stats::Stats};
 
 #[derive(
     Clone, Copy, Default, FromPrimitive, Serialize, Deserialize, Display, EnumCount, PartialEq,
@@ -34,44 +34,140 @@ pub enum Race {
     #[default]
     Human,
     Tiefling,
+    Aasimar,
+    Dwarf,
+    Goliath,
+    Halfling,
+    Orc,
 }
 
+const RACES_2014: [Race; 14] = {
+    use Race::*;
+    [
+        Dragonborn,
+        HillDwarf,
+        MountainDwarf,
+        HighElf,
+        WoodElf,
+        DarkElf,
+        ForestGnome,
+        RockGnome,
+        HalfElf,
+        HalfOrc,
+        LightfootHalfling,
+        StoutHalfling,
+        Human,
+        Tiefling,
+    ]
+};
+
+const RACES_2024: [Race; 13] = {
+    use Race::*;
+    [
+        Aasimar,
+        Dragonborn,
+        Dwarf,
+        HighElf,
+        WoodElf,
+        DarkElf,
+        ForestGnome,
+        RockGnome,
+        Goliath,
+        Halfling,
+        Human,
+        Orc,
+        Tiefling,
+    ]
+};
+
 impl Race {
     pub fn to_lookup_string(&self) -> &'static str {
         use Race::*;
         match self {
+            Aasimar => "aasimar",
             Dragonborn => "dragonborn",
-            HillDwarf | MountainDwarf => "dwarf",
+            HillDwarf | MountainDwarf | Dwarf => "dwarf",
             WoodElf | HighElf | DarkElf => "elf",
             HalfElf => "half-elf",
             HalfOrc => "half-orc",
             ForestGnome | RockGnome => "gnome",
-            LightfootHalfling | StoutHalfling => "halfling",
+            Goliath => "goliath",
+            LightfootHalfling | StoutHalfling | Halfling => "halfling",
             Human => "human",
+            Orc => "orc",
             Tiefling => "tiefling",
         }
     }
 
-    pub fn get_next(&self) -> Self {
-        let next_val = std::cmp::min(*self as u8 + 1, Race::COUNT as u8 - 1);
-        num_traits::FromPrimitive::from_u8(next_val).unwrap()
+    /// Get all races available under the given ruleset, in cycling order.
+    pub fn available(ruleset: Ruleset) -> &'static [Race] {
+        match ruleset {
+            Ruleset::Phb2014 => &RACES_2014,
+            Ruleset::Phb2024 => &RACES_2024,
+        }
+    }
+
+    /// Whether the race can be picked under the given ruleset.
+    pub fn is_available(&self, ruleset: Ruleset) -> bool {
+        Self::available(ruleset).contains(self)
+    }
+
+    /// Get the race that stands in for this one under the given ruleset, such as the
+    /// 2024 Dwarf for a 2014 Hill Dwarf.
+    ///
+    /// Races without a counterpart, such as the 2014 Half-Elf, are kept as they are.
+    pub fn for_ruleset(&self, ruleset: Ruleset) -> Self {
+        use Race::*;
+        match (self, ruleset) {
+            (HillDwarf | MountainDwarf, Ruleset::Phb2024) => Dwarf,
+            (LightfootHalfling | StoutHalfling, Ruleset::Phb2024) => Halfling,
+            (HalfOrc, Ruleset::Phb2024) => Orc,
+            (Dwarf, Ruleset::Phb2014) => HillDwarf,
+            (Halfling, Ruleset::Phb2014) => LightfootHalfling,
+            (Orc, Ruleset::Phb2014) => HalfOrc,
+            _ => *self,
+        }
     }
 
-    pub fn get_prev(&self) -> Self {
-        let prev_val = (*self as u8).saturating_sub(1);
-        num_traits::FromPrimitive::from_u8(prev_val).unwrap()
+    /// Get the next race available under the given ruleset.
+    ///
+    /// A race that does not belong to the ruleset moves to the first available one.
+    pub fn get_next(&self, ruleset: Ruleset) -> Self {
+        let list = Self::available(ruleset);
+        match list.iter().position(|r| r == self) {
+            Some(i) => list[std::cmp::min(i + 1, list.len() - 1)],
+            None => list[0],
+        }
     }
 
-    pub fn cycle_next(&mut self) {
-        *self = self.get_next()
+    /// Get the previous race available under the given ruleset.
+    ///
+    /// A race that does not belong to the ruleset moves to the first available one.
+    pub fn get_prev(&self, ruleset: Ruleset) -> Self {
+        let list = Self::available(ruleset);
+        match list.iter().position(|r| r == self) {
+            Some(i) => list[i.saturating_sub(1)],
+            None => list[0],
+        }
     }
 
-    pub fn cycle_prev(&mut self) {
-        *self = self.get_prev()
+    pub fn cycle_next(&mut self, ruleset: Ruleset) {
+        *self = self.get_next(ruleset)
     }
 
-    pub fn stats(&self) -> Stats {
+    pub fn cycle_prev(&mut self, ruleset: Ruleset) {
+        *self = self.get_prev(ruleset)
+    }
+
+    /// Get the ability score increases granted by the race.
+    ///
+    /// 2024 species grant no ability score increases, as those come from the background instead.
+    pub fn stats(&self, ruleset: Ruleset) -> Stats {
         use Race::*;
+        if ruleset == Ruleset::Phb2024 {
+            return Stats::zero();
+        }
+
         let strength = match self {
             MountainDwarf | Dragonborn | HalfOrc => 2,
             Human => 1,
@@ -116,10 +212,66 @@ impl Race {
         }
     }
 
+    /// Get the languages and tool, weapon and armor proficiencies granted by the race.
+    ///
+    /// 2024 species grant no languages; every character instead knows Common and two
+    /// other languages of their choice.
+    pub fn proficiencies(&self, ruleset: Ruleset) -> Proficiencies {
+        use Race::*;
+        const LANGUAGE: &str = "Language (choose one)";
+        if ruleset == Ruleset::Phb2024 {
+            return Proficiencies::from_lists(&["Common", LANGUAGE, LANGUAGE], &[], &[], &[]);
+        }
+
+        const DWARF_WEAPONS: [&str; 4] = ["Battleaxes", "Handaxes", "Light hammers", "Warhammers"];
+        const DWARF_TOOLS: [&str; 1] = ["Smith's, brewer's or mason's tools (choose one)"];
+        const ELF_WEAPONS: [&str; 4] = ["Longswords", "Shortswords", "Shortbows", "Longbows"];
+
+        match self {
+            Dragonborn => Proficiencies::from_lists(&["Common", "Draconic"], &[], &[], &[]),
+            HillDwarf => Proficiencies::from_lists(
+                &["Common", "Dwarvish"],
+                &DWARF_TOOLS,
+                &DWARF_WEAPONS,
+                &[],
+            ),
+            MountainDwarf => Proficiencies::from_lists(
+                &["Common", "Dwarvish"],
+                &DWARF_TOOLS,
+                &DWARF_WEAPONS,
+                &["Light armor", "Medium armor"],
+            ),
+            HighElf => {
+                Proficiencies::from_lists(&["Common", "Elvish", LANGUAGE], &[], &ELF_WEAPONS, &[])
+            }
+            WoodElf => Proficiencies::from_lists(&["Common", "Elvish"], &[], &ELF_WEAPONS, &[]),
+            DarkElf => Proficiencies::from_lists(
+                &["Common", "Elvish"],
+                &[],
+                &["Rapiers", "Shortswords", "Hand crossbows"],
+                &[],
+            ),
+            ForestGnome => Proficiencies::from_lists(&["Common", "Gnomish"], &[], &[], &[]),
+            RockGnome => {
+                Proficiencies::from_lists(&["Common", "Gnomish"], &["Tinker's tools"], &[], &[])
+            }
+            HalfElf => Proficiencies::from_lists(&["Common", "Elvish", LANGUAGE], &[], &[], &[]),
+            HalfOrc => Proficiencies::from_lists(&["Common", "Orc"], &[], &[], &[]),
+            LightfootHalfling | StoutHalfling => {
+                Proficiencies::from_lists(&["Common", "Halfling"], &[], &[], &[])
+            }
+            Human => Proficiencies::from_lists(&["Common", LANGUAGE], &[], &[], &[]),
+            Tiefling => Proficiencies::from_lists(&["Common", "Infernal"], &[], &[], &[]),
+            Aasimar | Dwarf | Goliath | Halfling | Orc => {
+                Proficiencies::from_lists(&["Common"], &[], &[], &[])
+            }
+        }
+    }
+
     pub fn health_bonus(&self) -> u32 {
         use Race::*;
         match self {
-            HillDwarf => 1,
+            HillDwarf | Dwarf => 1,
             _ => 0,
         }
     }
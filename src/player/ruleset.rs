@@ -0,0 +1,40 @@
+use num_derive::FromPrimitive;
+use serde_derive::{Deserialize, Serialize};
+use strum::{Display, EnumCount};
+
+/// The edition of the rules a character is built with.
+///
+/// Players saved before rulesets existed have no `ruleset` field and
+/// will load as [`Ruleset::Phb2014`].
+#[derive(
+    Clone, Copy, Default, FromPrimitive, Serialize, Deserialize, Display, EnumCount, PartialEq,
+)]
+pub enum Ruleset {
+    #[default]
+    #[strum(to_string = "2014")]
+    Phb2014,
+    #[strum(to_string = "2024")]
+    Phb2024,
+}
+
+impl Ruleset {
+    pub fn cycle_next(&mut self) {
+        let next_val = std::cmp::min(*self as u8 + 1, Ruleset::COUNT as u8 - 1);
+        *self = num_traits::FromPrimitive::from_u8(next_val).unwrap();
+    }
+
+    pub fn cycle_prev(&mut self) {
+        let prev_val = (*self as u8).saturating_sub(1);
+        *self = num_traits::FromPrimitive::from_u8(prev_val).unwrap();
+    }
+
+    /// The penalty applied to every d20 test for the given exhaustion level.
+    ///
+    /// The 2014 rules impose disadvantage instead of a flat penalty, so this is always zero for them.
+    pub fn exhaustion_penalty(&self, exhaustion: u32) -> u32 {
+        match self {
+            Ruleset::Phb2014 => 0,
+            Ruleset::Phb2024 => exhaustion * 2,
+        }
+    }
+}
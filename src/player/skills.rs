@@ -1,6 +1,7 @@
 use serde_derive::{Deserialize, Serialize};
+use strum::Display;
 
-#[derive(Clone, Copy, Serialize, Deserialize, Default)]
+#[derive(Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
 pub enum ProficiencyLevel {
     #[default]
     None,
@@ -21,6 +22,37 @@ impl ProficiencyLevel {
     }
 }
 
+/// Where a skill proficiency came from.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, PartialEq, Display)]
+pub enum ProficiencySource {
+    /// Set by hand in the proficiency menu, e.g. from a racial trait or feat.
+    #[default]
+    Manual,
+    /// Granted automatically by the player's background.
+    Background,
+    /// Chosen from the player's class skill list.
+    Class,
+}
+
+pub const ATHLETICS: usize = 0;
+pub const ACROBATICS: usize = 1;
+pub const SLEIGHT_OF_HAND: usize = 2;
+pub const STEALTH: usize = 3;
+pub const ARCANA: usize = 4;
+pub const HISTORY: usize = 5;
+pub const INVESTIGATION: usize = 6;
+pub const NATURE: usize = 7;
+pub const RELIGION: usize = 8;
+pub const ANIMAL_HANDLING: usize = 9;
+pub const INSIGHT: usize = 10;
+pub const MEDICINE: usize = 11;
+pub const PERCEPTION: usize = 12;
+pub const SURVIVAL: usize = 13;
+pub const DECEPTION: usize = 14;
+pub const INTIMIDATION: usize = 15;
+pub const PERFORMANCE: usize = 16;
+pub const PERSUASION: usize = 17;
+
 pub static SKILL_NAMES: [&str; 18] = [
     "Athletics",
     "Acrobatics",
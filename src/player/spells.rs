@@ -1,5 +1,7 @@
 use serde_derive::{Deserialize, Serialize};
 
+use super::ruleset::Ruleset;
+
 #[derive(Default, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct SpellSlots {
@@ -26,10 +28,12 @@ macro_rules! get_slots {
 }
 
 impl SpellSlots {
-    pub fn from(level: u32, class: &super::Class) -> Self {
+    pub fn from(level: u32, class: &super::Class, ruleset: Ruleset) -> Self {
         use super::Class::*;
         match class {
             Bard | Cleric | Wizard | Sorcerer | Druid => SpellSlots::from_full(level),
+            // 2024 paladins and rangers start casting at level 1
+            Ranger | Paladin if ruleset == Ruleset::Phb2024 => SpellSlots::from_half_2024(level),
             Artificer | Ranger | Paladin => SpellSlots::from_half(level),
             Warlock => SpellSlots::from_warlock(level),
             _ => Self::default(),
@@ -62,6 +66,17 @@ impl SpellSlots {
         }
     }
 
+    fn from_half_2024(level: u32) -> Self {
+        Self {
+            first: get_slots!(level, 1, 1, 3, 5),
+            second: get_slots!(level, 5, 5, 7),
+            third: get_slots!(level, 9, 9, 11),
+            fourth: get_slots!(level, 13, 15, 17),
+            fifth: get_slots!(level, 17, 19),
+            ..Self::default()
+        }
+    }
+
     fn from_warlock(level: u32) -> Self {
         Self {
             warlock: get_slots!(level, 1, 2, 11, 17),
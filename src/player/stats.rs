@@ -24,6 +24,20 @@ impl Default for Stats {
     }
 }
 
+impl Stats {
+    /// Create a set of stats with every value set to zero, useful for bonuses.
+    pub fn zero() -> Self {
+        Self {
+            strength: 0,
+            dexterity: 0,
+            constitution: 0,
+            intelligence: 0,
+            wisdom: 0,
+            charisma: 0,
+        }
+    }
+}
+
 impl std::ops::Index<usize> for Stats {
     type Output = u32;
 
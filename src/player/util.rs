@@ -1,3 +1,5 @@
+use rand::Rng;
+
 pub fn get_avg(dice: u32) -> u32 {
     match dice {
         6 => 4,
@@ -20,3 +22,43 @@ pub fn calculate_hp(level: u32, hit_dice: u32, constitution: u32, racial_bonus:
 
     (first_level + other_levels + racial_bonus).saturating_add_signed(total_con_mod)
 }
+
+/// Roll a single die with the given number of sides.
+pub fn roll(sides: u32) -> u32 {
+    rand::thread_rng().gen_range(1..=sides)
+}
+
+/// The most dice a single term of a dice expression can roll.
+const MAX_DICE: u32 = 100;
+
+/// The most sides a die in a dice expression can have.
+const MAX_SIDES: u32 = 1000;
+
+/// Roll a dice expression such as `1d6+1`, `2d4` or `3`.
+///
+/// Returns `None` if the expression could not be read, rolls more than `MAX_DICE` dice
+/// or a die with more than `MAX_SIDES` sides in one term, or adds up to more than fits
+/// in a `u32`, so text from lookups can't hang or crash the app.
+pub fn roll_dice(expression: &str) -> Option<u32> {
+    let expression: String = expression.split_whitespace().collect();
+    let mut total: u32 = 0;
+    for term in expression.split('+') {
+        let value = match term.split_once('d') {
+            Some((count, sides)) => {
+                let count = if count.is_empty() {
+                    1
+                } else {
+                    count.parse().ok()?
+                };
+                let sides: u32 = sides.parse().ok()?;
+                if sides == 0 || sides > MAX_SIDES || count > MAX_DICE {
+                    return None;
+                }
+                (0..count).map(|_| roll(sides)).sum()
+            }
+            None => term.parse::<u32>().ok()?,
+        };
+        total = total.checked_add(value)?;
+    }
+    Some(total)
+}
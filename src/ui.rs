@@ -1,13 +1,25 @@
 use ratatui::{
     layout::{Alignment, Rect},
     prelude::{Constraint, Direction, Frame, Layout},
-    style::Style,
-    widgets::{Block, Borders, Padding},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{
+        block::{Position, Title},
+        Block, Borders, Padding, Paragraph, Wrap,
+    },
 };
 
 use crate::{
     core::{App, LookupResult, Selected},
-    player::{class::Class, skills::SKILL_NAMES, spells::SpellSlots},
+    lookup::LookupEntry,
+    markup,
+    player::{
+        class::Class,
+        details::DETAIL_NAMES,
+        proficiencies::CATEGORY_NAMES,
+        skills::{ProficiencyLevel, ProficiencySource, SKILL_NAMES},
+        spells::SpellSlots,
+    },
     widgets::{
         info_bar::InfoBar, player_bar::PlayerBar, simple_popup::SimplePopup, stat_block::StatBlock,
         tab_panel::TabPanel, vec_popup::VecPopup, PopupSize,
@@ -22,13 +34,61 @@ fn show_quit_popup(app: &mut App, f: &mut Frame) {
         String::from("q/n - no"),
     ];
 
+    let popup = VecPopup::new(&data[..], PopupSize::Absolute(26, 7))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Unsaved Changes, Quit?")
+                .title_alignment(Alignment::Center)
+                .padding(Padding::uniform(1)),
+        )
+        .alignment(Alignment::Left);
+
+    app.popup_scroll_mut().update_frame_height(7);
+    f.render_widget(popup, f.size());
+}
+
+fn show_file_menu(app: &mut App, f: &mut Frame) {
+    let data = [
+        String::from("s - save"),
+        String::from("a - save as"),
+        String::from("r - rename"),
+        String::from("n - new character"),
+        String::from("b - restore backup"),
+    ];
+
+    let popup = VecPopup::new(&data[..], PopupSize::Absolute(24, 9))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("File")
+                .title_alignment(Alignment::Center)
+                .padding(Padding::uniform(1)),
+        )
+        .alignment(Alignment::Left);
+
+    app.popup_scroll_mut().update_frame_height(9);
+    f.render_widget(popup, f.size());
+}
+
+fn show_unsaved_changes_popup(app: &mut App, f: &mut Frame) {
+    let data = [
+        String::from("y - save first"),
+        String::from("s - don't save"),
+        String::from("q/n - cancel"),
+    ];
+
     let popup = VecPopup::new(&data[..], PopupSize::Absolute(24, 7))
         .bg(app.settings().popup_background.into())
         .fg(app.settings().popup_foreground.into())
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Really Quit?")
+                .title("Unsaved Changes")
                 .title_alignment(Alignment::Center)
                 .padding(Padding::uniform(1)),
         )
@@ -38,13 +98,60 @@ fn show_quit_popup(app: &mut App, f: &mut Frame) {
     f.render_widget(popup, f.size());
 }
 
+fn show_homebrew_discard_popup(app: &App, f: &mut Frame) {
+    let data = [
+        String::from("y - save"),
+        String::from("s - discard"),
+        String::from("q/n - keep editing"),
+    ];
+
+    let popup = VecPopup::new(&data[..], PopupSize::Absolute(24, 7))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Unsaved Homebrew")
+                .title_alignment(Alignment::Center)
+                .padding(Padding::uniform(1)),
+        )
+        .alignment(Alignment::Left);
+
+    // The editor's scroll is left alone so the same field is selected afterwards
+    f.render_widget(popup, f.size());
+}
+
+/// display the save as, rename or import prompt
+fn show_file_prompt(app: &mut App, f: &mut Frame) {
+    let title = match app.selected {
+        Some(Selected::SaveAs) => "Save as (.json or .yaml)",
+        Some(Selected::Import) => "Import player file into the characters folder",
+        _ => "Rename player",
+    };
+    let popup = SimplePopup::new(
+        &app.file_buffer,
+        PopupSize::Absolute(f.size().width - 10, 3),
+    )
+    .bg(app.settings().popup_background.into())
+    .fg(app.settings().popup_foreground.into())
+    .block(
+        Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL),
+    );
+
+    f.render_widget(popup, f.size());
+}
+
 /// Show the reference lookup menu.
 ///
 /// This could probably be moved to its own widget but I haven't done that yet.
 fn show_lookup(f: &mut Frame, app: &mut App) {
     let lookup = app.current_lookup.as_ref().unwrap();
 
-    // Store the frame height for use after the lookup reference is dropped
+    // Store the frame size for use after the lookup reference is dropped
+    let mut frame_width = 0;
     let frame_height = match lookup {
         LookupResult::Invalid(search) => {
             let text = format!("No results found for '{}'", search);
@@ -64,25 +171,54 @@ fn show_lookup(f: &mut Frame, app: &mut App) {
         }
         LookupResult::Success(entry) => {
             let entry = entry.as_ref();
-            let text = format!("{}\n{}", entry.description_short, entry.description);
-            let popup = SimplePopup::new(&text, PopupSize::Percentage(55, 65))
+            let (mut text, _) = markup::render(&entry.full_text());
+            for (i, link) in app.lookup_links.iter().enumerate() {
+                let style = if app.lookup_link == Some(i) {
+                    Style::default()
+                        .fg(app.settings().popup_background.into())
+                        .bg(app.settings().popup_foreground.into())
+                } else {
+                    Style::default().add_modifier(Modifier::UNDERLINED)
+                };
+                let line = std::mem::take(&mut text.lines[link.line]);
+                text.lines[link.line] = markup::style_range(line, link.range.clone(), style);
+            }
+            let highlight = Style::default()
+                .fg(app.settings().popup_background.into())
+                .bg(app.settings().highlight.into());
+            let text = highlight_terms(text, &app.search_terms, highlight);
+            let title = if app.lookup_links.is_empty() {
+                entry.name.clone()
+            } else {
+                format!("{} ({} links, tab)", entry.name, app.lookup_links.len())
+            };
+            let popup = SimplePopup::styled(text, PopupSize::Percentage(55, 65))
                 .bg(app.settings().popup_background.into())
                 .fg(app.settings().popup_foreground.into())
                 .wrap()
                 .scroll_to(app.popup_scroll().get_scroll())
-                .block(
-                    Block::default()
-                        .title(entry.name.clone())
-                        .title_alignment(Alignment::Center)
-                        .borders(Borders::ALL),
-                );
+                .block(entry_block(app, entry, title));
 
+            frame_width = popup.rect(f.size()).width - 2; // Border
             let frame_height = popup.rect(f.size()).height as u32 - 2; // Border
             f.render_widget(popup, f.size());
             frame_height
         }
         LookupResult::Completion(entries) => {
-            let lines: Vec<String> = entries.iter().map(|e| e.name.to_owned()).collect();
+            let width = entries.iter().map(|e| e.category.len()).max().unwrap_or(0);
+            let lines: Vec<String> = entries
+                .iter()
+                .map(|e| format!("{:<width$}  {}", e.category, e.name))
+                .collect();
+            let title = match (&app.selected, &app.lookup_category) {
+                (Some(Selected::FreeLookupSelect), Some(category)) => {
+                    format!("{} results found in {} (c)", entries.len(), category)
+                }
+                (Some(Selected::FreeLookupSelect), None) => {
+                    format!("{} results found (c)", entries.len())
+                }
+                _ => format!("{} results found", entries.len()),
+            };
             let popup = VecPopup::new(&lines, PopupSize::Percentage(55, 75))
                 .bg(app.settings().popup_background.into())
                 .fg(app.settings().popup_foreground.into())
@@ -93,7 +229,7 @@ fn show_lookup(f: &mut Frame, app: &mut App) {
                 )
                 .block(
                     Block::default()
-                        .title(format!("{} results found", entries.len()))
+                        .title(title)
                         .title_alignment(Alignment::Center)
                         .borders(Borders::ALL)
                         .padding(Padding::vertical(1)),
@@ -104,7 +240,8 @@ fn show_lookup(f: &mut Frame, app: &mut App) {
             frame_height
         }
         LookupResult::Files(files) => {
-            let popup = VecPopup::new(files, PopupSize::Percentage(55, 75))
+            let previews: Vec<String> = files.iter().map(|(_, preview)| preview.clone()).collect();
+            let popup = VecPopup::new(&previews, PopupSize::Percentage(55, 75))
                 .bg(app.settings().popup_background.into())
                 .fg(app.settings().popup_foreground.into())
                 .highlight(
@@ -114,7 +251,7 @@ fn show_lookup(f: &mut Frame, app: &mut App) {
                 .scroll_to(app.popup_scroll().get_scroll())
                 .block(
                     Block::default()
-                        .title("Load Player")
+                        .title("Load Player (i to import a file)")
                         .title_alignment(Alignment::Center)
                         .borders(Borders::ALL)
                         .padding(Padding::vertical(1)),
@@ -127,6 +264,120 @@ fn show_lookup(f: &mut Frame, app: &mut App) {
     };
 
     app.popup_scroll_mut().update_frame_height(frame_height);
+    app.popup_scroll_mut().update_frame_width(frame_width);
+}
+
+/// Get the bordered block of a lookup entry, with its source and tags along the bottom.
+///
+/// Entries from sources that are not allowed are marked in the title.
+fn entry_block(app: &App, entry: &LookupEntry, mut title: String) -> Block<'static> {
+    let pinned = app
+        .player
+        .pinned
+        .iter()
+        .any(|n| n.eq_ignore_ascii_case(&entry.name));
+    let tags: Vec<&str> = [
+        (entry.source.as_str(), !entry.source.is_empty()),
+        ("homebrew", entry.homebrew),
+        ("bookmarked", app.history.is_bookmarked(&entry.name)),
+        ("pinned", pinned),
+    ]
+    .into_iter()
+    .filter_map(|(tag, shown)| shown.then_some(tag))
+    .collect();
+
+    if !entry.is_allowed(&app.settings().allowed_sources) {
+        title = format!("{} [source not allowed]", title);
+    }
+    Block::default()
+        .title(title)
+        .title(
+            Title::from(tags.join(", "))
+                .position(Position::Bottom)
+                .alignment(Alignment::Right),
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+}
+
+/// Draw the most recent lookup entry in the docked pane beside the tab panel.
+fn draw_docked_lookup(app: &mut App, f: &mut Frame, rect: Rect) {
+    let style = Style::default()
+        .fg(app.settings().foreground.into())
+        .bg(app.settings().background.into());
+    let width = rect.width.saturating_sub(2); // Border
+    let mut rows = 1;
+    let paragraph = match &app.docked_entry {
+        Some(entry) => {
+            let (mut text, links) = markup::render(&entry.full_text());
+            rows = text
+                .lines
+                .iter()
+                .map(|line| markup::wrapped_rows(&markup::plain(line), width))
+                .sum();
+            for link in links {
+                let line = std::mem::take(&mut text.lines[link.line]);
+                text.lines[link.line] = markup::style_range(
+                    line,
+                    link.range,
+                    Style::default().add_modifier(Modifier::UNDERLINED),
+                );
+            }
+            let title = format!("{} (D)", entry.name);
+            Paragraph::new(text).block(entry_block(app, entry, title))
+        }
+        None => Paragraph::new("Lookups will be shown here").block(
+            Block::default()
+                .title("Lookup (D)")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        ),
+    };
+
+    let paragraph = paragraph
+        .style(style)
+        .wrap(Wrap { trim: false })
+        .scroll((app.docked_scroll().get_scroll() as u16, 0));
+    f.render_widget(paragraph, rect);
+    let scroll = app.docked_scroll_mut();
+    scroll.update_frame_height(rect.height.saturating_sub(2) as u32);
+    scroll.set_max(rows);
+}
+
+/// Style every word in the text that starts with one of the given search terms.
+fn highlight_terms<'a>(text: Text<'a>, terms: &[String], style: Style) -> Text<'a> {
+    if terms.is_empty() {
+        return text;
+    }
+
+    let lines: Vec<Line> = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = vec![];
+            for span in line.spans {
+                let content = span.content.as_ref();
+                let mut last = 0;
+                let words = content
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|w| !w.is_empty())
+                    .map(|w| (w.as_ptr() as usize - content.as_ptr() as usize, w));
+
+                for (start, word) in words {
+                    let lower = word.to_lowercase();
+                    if terms.iter().any(|t| lower.starts_with(t.as_str())) {
+                        spans.push(Span::styled(content[last..start].to_string(), span.style));
+                        spans.push(Span::styled(word.to_string(), span.style.patch(style)));
+                        last = start + word.len();
+                    }
+                }
+                spans.push(Span::styled(content[last..].to_string(), span.style));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    Text::from(lines)
 }
 
 /// Get the ordinal suffix corresponding to the given digit
@@ -221,7 +472,7 @@ fn show_free_lookup_prompt(app: &mut App, f: &mut Frame) {
     .fg(app.settings().popup_foreground.into())
     .block(
         Block::default()
-            .title("Lookup")
+            .title("Lookup (start with / to search descriptions)")
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL),
     );
@@ -229,14 +480,141 @@ fn show_free_lookup_prompt(app: &mut App, f: &mut Frame) {
     f.render_widget(popup, f.size());
 }
 
+/// display the menu of pinned, bookmarked and recent lookup entries
+fn show_history(app: &mut App, f: &mut Frame) {
+    let lines: Vec<String> = app
+        .history_lines()
+        .into_iter()
+        .map(|(kind, name)| format!("{}: {}", kind, name))
+        .collect();
+
+    let mut popup = VecPopup::new(&lines, PopupSize::Percentage(45, 55))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .block(
+            Block::default()
+                .title("Lookups (enter to open, d to remove)")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    if !lines.is_empty() {
+        popup = popup.highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        );
+    }
+
+    let frame_height = popup.rect(f.size()).height as u32 - 2;
+    f.render_widget(popup, f.size());
+    app.popup_scroll_mut().update_frame_height(frame_height);
+}
+
+/// display the homebrew lookup entry editor
+fn show_homebrew_editor(app: &mut App, f: &mut Frame) {
+    let entry = &app.homebrew_entry;
+    let mut lines = vec![
+        format!("Name: {}", entry.name),
+        format!("Category: {}", entry.category),
+        format!("Short description: {}", entry.description_short),
+        format!("Homebrew: {}", if entry.homebrew { "yes" } else { "no" }),
+        String::from("Description:"),
+    ];
+    lines.extend(
+        entry
+            .description
+            .split('\n')
+            .map(|line| format!("  {}", line)),
+    );
+
+    let popup = VecPopup::new(&lines, PopupSize::Percentage(60, 70))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .highlight(
+            app.popup_scroll().get_line(),
+            if app.editing {
+                app.settings().highlight.into()
+            } else {
+                app.settings().popup_foreground.into()
+            },
+        )
+        .block(
+            Block::default()
+                .title("Homebrew Entry (enter to edit, s to save)")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    let frame_height = popup.rect(f.size()).height as u32 - 2;
+    f.render_widget(popup, f.size());
+    app.popup_scroll_mut().update_frame_height(frame_height);
+}
+
 /// display the proficiency popup menu
 fn show_proficiencies(app: &mut App, f: &mut Frame) {
+    let issues = app.player.skill_issues();
     let lines: Vec<String> = app
         .player
         .get_skills()
         .iter()
         .enumerate()
-        .map(|(i, skill)| format!("{}: {:+}", SKILL_NAMES[i], skill))
+        .map(|(i, skill)| {
+            let source = match app.player.skills[i] {
+                ProficiencyLevel::None => String::new(),
+                ProficiencyLevel::Double => {
+                    format!(" [{}, Expertise]", app.player.skill_sources[i])
+                }
+                _ => format!(" [{}]", app.player.skill_sources[i]),
+            };
+            let flag = if issues.iter().any(|(s, _)| *s == Some(i)) {
+                " !"
+            } else {
+                ""
+            };
+            format!("{}: {:+}{}{}", SKILL_NAMES[i], skill, source, flag)
+        })
+        .collect();
+
+    let title = if issues.is_empty() {
+        String::from("Proficiencies")
+    } else {
+        format!("Proficiencies ({} issues, v to view)", issues.len())
+    };
+
+    let popup = VecPopup::new(&lines, PopupSize::Percentage(35, 55))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    app.popup_scroll_mut()
+        .update_frame_height(popup.rect(f.size()).height as u32 - 2);
+    f.render_widget(popup, f.size());
+}
+
+/// display the class skill choice menu
+fn show_class_skills(app: &mut App, f: &mut Frame) {
+    let (count, choices) = app.player.class.skill_choices(app.player.ruleset);
+    let lines: Vec<String> = choices
+        .iter()
+        .map(|skill| {
+            let mark = match app.player.skill_sources[*skill] {
+                ProficiencySource::Class => "[x]",
+                _ if app.player.skills[*skill] != ProficiencyLevel::None => "[-]",
+                _ => "[ ]",
+            };
+            format!("{} {}", mark, SKILL_NAMES[*skill])
+        })
         .collect();
 
     let popup = VecPopup::new(&lines, PopupSize::Percentage(35, 55))
@@ -249,7 +627,11 @@ fn show_proficiencies(app: &mut App, f: &mut Frame) {
         )
         .block(
             Block::default()
-                .title("Proficiencies")
+                .title(format!(
+                    "Choose {} from this list ({} left)",
+                    count,
+                    count.saturating_sub(app.player.class_skill_count())
+                ))
                 .title_alignment(Alignment::Center)
                 .borders(Borders::ALL),
         );
@@ -259,7 +641,83 @@ fn show_proficiencies(app: &mut App, f: &mut Frame) {
     f.render_widget(popup, f.size());
 }
 
+/// display the languages and tool, weapon and armor proficiency menu
+fn show_training(app: &mut App, f: &mut Frame) {
+    let category = app.index;
+    let lines = app.player.proficiencies.nth(category);
+
+    let title = match &app.last_roll {
+        Some(roll) => roll.clone(),
+        None => CATEGORY_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                if i as u32 == category {
+                    format!("[{}]", name)
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+
+    let mut popup = VecPopup::new(lines, PopupSize::Percentage(45, 55))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    if !lines.is_empty() {
+        popup = popup.highlight(
+            app.popup_scroll().get_line(),
+            if app.editing {
+                app.settings().highlight.into()
+            } else {
+                app.settings().popup_foreground.into()
+            },
+        );
+    }
+
+    let frame_height = popup.rect(f.size()).height as u32 - 2;
+    f.render_widget(popup, f.size());
+    app.popup_scroll_mut().update_frame_height(frame_height);
+}
+
+/// display the weapon attack menu
+fn show_attacks(app: &mut App, f: &mut Frame) {
+    let lines = app.attack_lines();
+
+    let mut popup = VecPopup::new(&lines, PopupSize::Percentage(45, 45))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .block(
+            Block::default()
+                .title("Attacks")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    if !lines.is_empty() {
+        popup = popup.highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        );
+    }
+
+    let frame_height = popup.rect(f.size()).height as u32 - 2;
+    f.render_widget(popup, f.size());
+    app.popup_scroll_mut().update_frame_height(frame_height);
+}
+
 fn show_error_popup(app: &mut App, f: &mut Frame) {
+    let title = app.message_title.clone().unwrap_or(String::from("Error"));
     let popup = SimplePopup::new(
         app.error
             .as_ref()
@@ -272,7 +730,7 @@ fn show_error_popup(app: &mut App, f: &mut Frame) {
     .wrap()
     .block(
         Block::default()
-            .title("Error")
+            .title(title)
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL),
     );
@@ -309,11 +767,27 @@ fn stat_tab_layout(parent: Rect) -> (Rect, Rect) {
     (stat_chunk[0], chunks[1])
 }
 
-/// Get the player bar, info bar, stat block, and tab pane rects.
-fn layouts(viewport: Rect) -> (Rect, Rect, Rect, Rect) {
+/// Split the tab chunk into the tab pane and the docked lookup pane beside it.
+fn docked_layout(parent: Rect) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(parent);
+
+    (chunks[0], chunks[1])
+}
+
+/// Get the player bar, info bar, stat block, and tab pane rects, as well as the docked
+/// lookup pane rect if it is shown.
+fn layouts(viewport: Rect, docked: bool) -> (Rect, Rect, Rect, Rect, Option<Rect>) {
     let (player, info, stat_tab) = main_layout(viewport);
     let (stat, tab) = stat_tab_layout(stat_tab);
-    (player, info, stat, tab)
+    if docked {
+        let (tab, dock) = docked_layout(tab);
+        (player, info, stat, tab, Some(dock))
+    } else {
+        (player, info, stat, tab, None)
+    }
 }
 
 /// Get the height of the tab pane given the specified viewport height
@@ -326,10 +800,11 @@ pub fn tab_pane_height(viewport_height: u16) -> u16 {
 /// Draw all UI widgets that are always present.
 fn draw_static_widgets(app: &mut App, f: &mut Frame) {
     // Create layouts
-    let (player_rect, info_rect, stat_rect, tab_rect) = layouts(f.size());
+    let (player_rect, info_rect, stat_rect, tab_rect, dock_rect) = layouts(f.size(), app.docked);
 
     // Render player bar
     let mut player_bar = PlayerBar::new(&app.player)
+        .modified(app.has_unsaved_changes())
         .fg(app.settings().foreground.into())
         .bg(app.settings().background.into());
     if let Some(Selected::TopBarItem) = app.selected {
@@ -345,7 +820,16 @@ fn draw_static_widgets(app: &mut App, f: &mut Frame) {
     f.render_widget(player_bar, player_rect);
 
     // Render stat block
-    let mut stat_block = StatBlock::new(&app.player.stats)
+    let (stats, title) = match (app.current_companion(), app.player.wild_shape_form()) {
+        (Some(companion), _) => (companion.stats.clone(), companion.name.clone()),
+        (None, Some(form)) => (
+            app.player.effective_stats(),
+            format!("Stats ({})", form.name),
+        ),
+        (None, None) => (app.player.stats.clone(), String::from("Stats")),
+    };
+    let mut stat_block = StatBlock::new(&stats)
+        .title(title)
         .fg(app.settings().foreground.into())
         .bg(app.settings().background.into());
     if let Some(Selected::StatItem) = app.selected {
@@ -362,6 +846,7 @@ fn draw_static_widgets(app: &mut App, f: &mut Frame) {
 
     // Render player info bar
     let mut info_bar = InfoBar::new(&app.player)
+        .companion(app.current_companion())
         .fg(app.settings().foreground.into())
         .bg(app.settings().background.into());
     if let Some(Selected::InfoItem) = app.selected {
@@ -378,6 +863,8 @@ fn draw_static_widgets(app: &mut App, f: &mut Frame) {
 
     // Render the tab panel
     let mut tab_block = TabPanel::new(&app.player, app.current_tab)
+        .detail_field(app.detail_field)
+        .companion(app.current_companion())
         .fg(app.settings().foreground.into())
         .bg(app.settings().background.into())
         .select(app.settings().tab_select.into())
@@ -393,6 +880,144 @@ fn draw_static_widgets(app: &mut App, f: &mut Frame) {
         )
     }
     f.render_widget(tab_block, tab_rect);
+
+    // Render the docked lookup pane
+    if let Some(dock_rect) = dock_rect {
+        draw_docked_lookup(app, f, dock_rect);
+    }
+}
+
+/// display the background suggestions for the current detail field
+fn show_suggestions(app: &mut App, f: &mut Frame) {
+    let lines: Vec<String> = app
+        .detail_suggestions()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("{}. {}", i + 1, s))
+        .collect();
+
+    let popup = VecPopup::new(&lines, PopupSize::Percentage(60, 40))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        )
+        .block(
+            Block::default()
+                .title(format!(
+                    "{} {} (r to roll)",
+                    app.player.background, DETAIL_NAMES[app.detail_field]
+                ))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    app.popup_scroll_mut()
+        .update_frame_height(popup.rect(f.size()).height as u32 - 2);
+    f.render_widget(popup, f.size());
+}
+
+/// display the companion menu
+fn show_companions(app: &mut App, f: &mut Frame) {
+    let lines = app.companion_lines();
+
+    let popup = VecPopup::new(&lines, PopupSize::Percentage(40, 40))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        )
+        .block(
+            Block::default()
+                .title("Companions")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    app.popup_scroll_mut()
+        .update_frame_height(popup.rect(f.size()).height as u32 - 2);
+    f.render_widget(popup, f.size());
+}
+
+/// display the magic item menu
+fn show_magic_items(app: &mut App, f: &mut Frame) {
+    let lines = app.magic_item_lines();
+
+    let mut popup = VecPopup::new(&lines, PopupSize::Percentage(60, 40))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.popup_scroll().get_scroll())
+        .block(
+            Block::default()
+                .title(app.magic_items_title())
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    if !lines.is_empty() {
+        popup = popup.highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        );
+    }
+
+    let frame_height = popup.rect(f.size()).height as u32 - 2;
+    f.render_widget(popup, f.size());
+    app.popup_scroll_mut().update_frame_height(frame_height);
+}
+
+/// Show the backups of the player's file beside the changes restoring the selected one would make.
+fn show_backups(app: &mut App, f: &mut Frame) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(10),
+            Constraint::Percentage(25),
+            Constraint::Percentage(55),
+            Constraint::Percentage(10),
+        ])
+        .split(f.size());
+
+    let lines = app.backup_lines();
+    let backups = VecPopup::new(&lines, PopupSize::Percentage(100, 70))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .highlight(
+            app.popup_scroll().get_line(),
+            app.settings().popup_foreground.into(),
+        )
+        .scroll_to(app.popup_scroll().get_scroll())
+        .block(
+            Block::default()
+                .title("Restore Backup")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    let diff = match app.backup_diff() {
+        [] => vec![String::from("No changes")],
+        diff => diff.to_vec(),
+    };
+    let changes = VecPopup::new(&diff, PopupSize::Percentage(100, 70))
+        .bg(app.settings().popup_background.into())
+        .fg(app.settings().popup_foreground.into())
+        .scroll_to(app.index)
+        .block(
+            Block::default()
+                .title("Changes (- current, + backup)")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL),
+        );
+
+    let frame_height = backups.rect(chunks[1]).height as u32 - 2;
+    f.render_widget(backups, chunks[1]);
+    f.render_widget(changes, chunks[2]);
+    app.popup_scroll_mut().update_frame_height(frame_height);
 }
 
 fn draw_background_color(app: &mut App, f: &mut Frame) {
@@ -414,10 +1039,26 @@ pub fn render(app: &mut App, f: &mut Frame) {
             | Selected::Load,
         ) => show_lookup(f, app),
         Some(Selected::Quitting) => show_quit_popup(app, f),
+        Some(Selected::FileMenu) => show_file_menu(app, f),
+        Some(Selected::UnsavedChanges) => show_unsaved_changes_popup(app, f),
+        Some(Selected::Backups) => show_backups(app, f),
+        Some(Selected::SaveAs | Selected::Rename | Selected::Import) => show_file_prompt(app, f),
         Some(Selected::SpellSlots) => show_spell_slots(app, f),
         Some(Selected::Funds) => show_funds(app, f),
         Some(Selected::FreeLookup) => show_free_lookup_prompt(app, f),
+        Some(Selected::Homebrew) => show_homebrew_editor(app, f),
+        Some(Selected::HomebrewDiscard) => {
+            show_homebrew_editor(app, f);
+            show_homebrew_discard_popup(app, f);
+        }
+        Some(Selected::History) => show_history(app, f),
         Some(Selected::Proficiency) => show_proficiencies(app, f),
+        Some(Selected::ClassSkills) => show_class_skills(app, f),
+        Some(Selected::Training) => show_training(app, f),
+        Some(Selected::Attacks) => show_attacks(app, f),
+        Some(Selected::Suggestions) => show_suggestions(app, f),
+        Some(Selected::Companions) => show_companions(app, f),
+        Some(Selected::MagicItems) => show_magic_items(app, f),
         Some(Selected::Error) => show_error_popup(app, f),
         _ => {}
     }
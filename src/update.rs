@@ -1,15 +1,38 @@
-use std::path::PathBuf;
-
 use crate::{
-    core::{App, ControlType, LookupResult, Selected, Tab},
+    core::{App, ControlType, LookupResult, PlayerChange, Selected, Tab},
     lookup::Lookup,
-    player::{class::Class, skills::ProficiencyLevel},
+    player::{
+        class::Class,
+        companion::{Companion, CompanionKind},
+        details::DETAIL_NAMES,
+        proficiencies::TOOLS,
+        skills::ProficiencyLevel,
+        util::roll,
+    },
 };
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 
 /// Process the given key event and update that app's state accordingly.
 pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result<()> {
+    // File names can contain keys that are usually shortcuts, such as `q`
+    if let Some(Selected::SaveAs | Selected::Rename | Selected::Import) = app.selected {
+        return update_file_prompt(app, key_event);
+    }
+    // Closing the homebrew editor asks first if there are unsaved edits
+    if let Some(Selected::HomebrewDiscard) = app.selected {
+        return update_homebrew_discard(app, lookup, key_event);
+    }
+    let closing = matches!(key_event.code, KeyCode::Esc | KeyCode::Char('q'));
+    if app.selected == Some(Selected::Homebrew)
+        && !app.editing
+        && closing
+        && app.has_unsaved_homebrew()
+    {
+        app.selected = Some(Selected::HomebrewDiscard);
+        return Ok(());
+    }
+
     if app.editing {
         if key_event.code == KeyCode::Esc {
             app.editing = false;
@@ -35,11 +58,16 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Right => app.index = (app.index + 1).min(app.current_tab()[app.tab_scroll().get_line() as usize].len() as u32),
                 _ => {},
             }
-        } else {
-            if key_event.code == KeyCode::Enter {
-                app.editing = false;
-                return Ok(());
+        } else if key_event.code == KeyCode::Enter && !app.editing_multiline() {
+            app.editing = false;
+            // Prompt for class skills once a new class has been picked
+            if app.selected == Some(Selected::TopBarItem) && app.index == 3 {
+                let (count, _) = app.player.class.skill_choices(app.player.ruleset);
+                if app.player.class_skill_count() < count {
+                    app.choose_class_skills();
+                }
             }
+            return Ok(());
         }
         match app.get_selected_type() {
             Some(ControlType::TextInput(text)) => {
@@ -75,6 +103,7 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                             text.push(c);
                         }
                     }
+                    KeyCode::Enter if !in_tab => text.push('\n'),
                     KeyCode::Tab if !app.current_tab().is_empty() && in_tab => {
                         if let Some(Selected::TabItem) = app.selected {
                             app.complete_current_selection(lookup)?;
@@ -146,7 +175,9 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 if let Some(Selected::Quitting) = app.selected {
                     break 'quit;
                 }
-                if app.selected.is_none() {
+                if app.selected.is_none() && !app.has_unsaved_changes() {
+                    app.quit();
+                } else if app.selected.is_none() {
                     app.selected = Some(Selected::Quitting);
                     app.current_lookup = None;
                 } else {
@@ -158,13 +189,14 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
             KeyCode::Char('1') => app.update_tab(Tab::Notes),
             KeyCode::Char('2') => app.update_tab(Tab::Inventory),
             KeyCode::Char('3') => app.update_tab(Tab::Spells),
+            KeyCode::Char('4') => app.update_tab(Tab::Details),
             _ => {}
         }
 
         match app.selected {
             Some(Selected::TopBarItem) => match key_event.code {
                 KeyCode::Char('h') => app.index = app.index.saturating_sub(1),
-                KeyCode::Char('l') => app.index = std::cmp::min(4, app.index + 1),
+                KeyCode::Char('l') => app.index = std::cmp::min(5, app.index + 1),
                 KeyCode::Enter => app.editing = true,
                 _ => {}
             },
@@ -174,10 +206,21 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Enter => app.editing = true,
                 _ => {}
             },
+            Some(Selected::InfoItem) if app.companion.is_some() => match key_event.code {
+                KeyCode::Char('h') => app.index = app.index.saturating_sub(1),
+                KeyCode::Char('l') => app.index = std::cmp::min(5, app.index + 1),
+                KeyCode::Char('r') if app.index == 1 => {
+                    let companion = &mut app.player.companions[app.companion.unwrap()];
+                    companion.hp = companion.max_hp;
+                }
+                KeyCode::Enter => app.editing = true,
+                _ => {}
+            },
             Some(Selected::InfoItem) => match key_event.code {
                 KeyCode::Char('h') => app.index = app.index.saturating_sub(1),
-                KeyCode::Char('l') => app.index = std::cmp::min(6, app.index + 1),
+                KeyCode::Char('l') => app.index = std::cmp::min(7, app.index + 1),
                 KeyCode::Char('r') if app.index == 0 => app.player.hp = app.player.max_hp,
+                KeyCode::Char('r') if app.index == 3 => app.recalculate_ac(),
                 KeyCode::Enter => app.editing = true,
                 _ => {}
             },
@@ -198,6 +241,32 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 }
                 KeyCode::Char('K') => app.tab_scroll_mut().scroll_up(10),
                 KeyCode::Char('J') => app.tab_scroll_mut().scroll_down(10),
+                KeyCode::Char('(') if app.docked => app.docked_scroll_mut().move_up(1),
+                KeyCode::Char(')') if app.docked => app.docked_scroll_mut().move_down(1),
+                KeyCode::Char('{') if app.docked => app.docked_scroll_mut().move_up(10),
+                KeyCode::Char('}') if app.docked => app.docked_scroll_mut().move_down(10),
+                KeyCode::Char('H')
+                    if app.current_tab == Tab::Details && app.companion.is_none() =>
+                {
+                    app.update_detail_field(app.detail_field.saturating_sub(1))
+                }
+                KeyCode::Char('L')
+                    if app.current_tab == Tab::Details && app.companion.is_none() =>
+                {
+                    app.update_detail_field(std::cmp::min(
+                        DETAIL_NAMES.len() - 1,
+                        app.detail_field + 1,
+                    ))
+                }
+                KeyCode::Char('s')
+                    if app.current_tab == Tab::Details && app.companion.is_none() =>
+                {
+                    if let Some(suggestions) = app.detail_suggestions() {
+                        app.popup_scroll_mut().reset();
+                        app.popup_scroll_mut().set_max(suggestions.len() as u32);
+                        app.selected = Some(Selected::Suggestions);
+                    }
+                }
                 KeyCode::Char('i') => app.editing = true,
                 KeyCode::Char('a') => {
                     app.index = (app.index + 1).min(app.current_tab()[app.tab_scroll().get_line() as usize].len() as u32);
@@ -223,7 +292,7 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 }
                 KeyCode::Char('d') if !app.current_tab().is_empty() => {
                     app.delete_item_from_tab();
-                    if app.current_tab().len() > 0 {
+                    if !app.current_tab().is_empty() {
                         app.index = (app.index).min(app.current_tab()[app.tab_scroll().get_line() as usize].len() as u32);
                     } else {
                         app.index = 0;
@@ -232,12 +301,12 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Char('x') => {
                     let line = app.tab_scroll().get_line() as usize;
                     let index = app.index as usize;
-                    if app.current_tab()[line].len() > 0 && index < app.current_tab()[line].len() {
+                    if !app.current_tab()[line].is_empty() && index < app.current_tab()[line].len() {
                         app.current_tab_mut()[line].remove(index);
                         app.index = index.min(app.current_tab()[line].len()) as u32;
-                    } else if app.current_tab()[line].len() == 0 {
+                    } else if app.current_tab()[line].is_empty() {
                         app.delete_item_from_tab();
-                        if app.current_tab().len() > 0 {
+                        if !app.current_tab().is_empty() {
                             app.index = (app.index).min(app.current_tab()[app.tab_scroll().get_line() as usize].len() as u32);
                         } else {
                             app.index = 0;
@@ -278,19 +347,77 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Char('q' | 'n') => app.selected = None,
                 KeyCode::Char('s') => app.quit(),
                 KeyCode::Char('y') => {
-                    if app.player.name.is_empty() {
-                        app.player.name = String::from("player");
-                    }
                     app.save_player()?;
                     app.quit();
                 }
                 _ => {}
             },
+            Some(Selected::FileMenu) => match key_event.code {
+                KeyCode::Char('s') => {
+                    app.selected = None;
+                    app.save_player()?;
+                }
+                KeyCode::Char('a') => {
+                    app.file_buffer = app
+                        .save_path()
+                        .map(|p| p.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    app.selected = Some(Selected::SaveAs);
+                }
+                KeyCode::Char('r') => {
+                    app.file_buffer = app.player.name.clone();
+                    app.selected = Some(Selected::Rename);
+                }
+                KeyCode::Char('n') => app.change_player(PlayerChange::New)?,
+                KeyCode::Char('b') => app.show_backups()?,
+                _ => {}
+            },
+            Some(Selected::Backups) => match key_event.code {
+                KeyCode::Char('k') => {
+                    app.popup_scroll_mut().scroll_up(1);
+                    app.index = 0;
+                }
+                KeyCode::Char('j') => {
+                    app.popup_scroll_mut().scroll_down(1);
+                    app.index = 0;
+                }
+                KeyCode::Char('K') => app.index = app.index.saturating_sub(10),
+                KeyCode::Char('J') => {
+                    let len = app.backup_diff().len() as u32;
+                    app.index = std::cmp::min(app.index + 10, len.saturating_sub(1));
+                }
+                KeyCode::Enter => app.restore_backup()?,
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    app.selected = None;
+                    app.backups.clear();
+                }
+                _ => {}
+            },
+            // Handled by `update_file_prompt()` and `update_homebrew_discard()`
+            Some(
+                Selected::SaveAs | Selected::Rename | Selected::Import | Selected::HomebrewDiscard,
+            ) => {}
+            Some(Selected::UnsavedChanges) => match key_event.code {
+                KeyCode::Char('q' | 'n') | KeyCode::Esc => app.cancel_player_change(),
+                KeyCode::Char('s') => app.finish_player_change(false)?,
+                KeyCode::Char('y') => app.finish_player_change(true)?,
+                _ => {}
+            },
             Some(Selected::ItemLookup(_)) => match key_event.code {
+                KeyCode::Char('D') => app.dock_lookup(),
+                KeyCode::Char('e') => app.edit_homebrew(),
+                KeyCode::Char('b') => app.toggle_bookmark()?,
+                KeyCode::Char('p') => app.toggle_pin(),
+                KeyCode::Char('a') => app.add_monster_companion(CompanionKind::Companion),
+                KeyCode::Char('w') => app.add_monster_companion(CompanionKind::WildShape),
                 KeyCode::Char('k') => app.popup_scroll_mut().move_up(1),
                 KeyCode::Char('j') => app.popup_scroll_mut().move_down(1),
                 KeyCode::Char('K') => app.popup_scroll_mut().move_up(10),
                 KeyCode::Char('J') => app.popup_scroll_mut().move_down(10),
+                KeyCode::Tab => app.select_link(true),
+                KeyCode::BackTab => app.select_link(false),
+                KeyCode::Enter => app.follow_link(lookup)?,
+                KeyCode::Backspace => app.lookup_back(lookup)?,
                 KeyCode::Char('q') => {
                     app.selected = Some(Selected::TabItem);
                     app.current_lookup = None;
@@ -298,10 +425,20 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 _ => {}
             },
             Some(Selected::ClassLookup) => match key_event.code {
+                KeyCode::Char('D') => app.dock_lookup(),
+                KeyCode::Char('e') => app.edit_homebrew(),
+                KeyCode::Char('b') => app.toggle_bookmark()?,
+                KeyCode::Char('p') => app.toggle_pin(),
+                KeyCode::Char('a') => app.add_monster_companion(CompanionKind::Companion),
+                KeyCode::Char('w') => app.add_monster_companion(CompanionKind::WildShape),
                 KeyCode::Char('k') => app.popup_scroll_mut().move_up(1),
                 KeyCode::Char('j') => app.popup_scroll_mut().move_down(1),
                 KeyCode::Char('K') => app.popup_scroll_mut().move_up(10),
                 KeyCode::Char('J') => app.popup_scroll_mut().move_down(10),
+                KeyCode::Tab => app.select_link(true),
+                KeyCode::BackTab => app.select_link(false),
+                KeyCode::Enter => app.follow_link(lookup)?,
+                KeyCode::Backspace => app.lookup_back(lookup)?,
                 KeyCode::Char('q') => {
                     app.selected = None;
                     app.current_lookup = None;
@@ -359,6 +496,28 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
                 _ => {}
             },
+            Some(Selected::History) => match key_event.code {
+                KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                KeyCode::Char('K') => app.popup_scroll_mut().scroll_up(10),
+                KeyCode::Char('J') => app.popup_scroll_mut().scroll_down(10),
+                KeyCode::Char('d') => app.remove_history_line()?,
+                KeyCode::Enter => app.open_history_line(lookup)?,
+                _ => {}
+            },
+            Some(Selected::Homebrew) => match key_event.code {
+                KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                KeyCode::Char('e') | KeyCode::Enter => {
+                    if app.popup_scroll().get_line() == 3 {
+                        app.homebrew_entry.homebrew = !app.homebrew_entry.homebrew;
+                    } else {
+                        app.editing = true;
+                    }
+                }
+                KeyCode::Char('s') => app.save_homebrew(lookup)?,
+                _ => {}
+            },
             Some(Selected::FreeLookup) => match key_event.code {
                 KeyCode::Backspace => {
                     app.lookup_buffer.pop();
@@ -367,8 +526,7 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                     app.lookup_buffer.push(c);
                 }
                 KeyCode::Tab | KeyCode::Enter => {
-                    app.current_lookup =
-                        Some(app.get_completion(&app.lookup_buffer.clone(), lookup)?);
+                    app.current_lookup = Some(app.free_lookup(lookup)?);
                     app.selected = Some(Selected::FreeLookupSelect);
                 }
                 _ => {}
@@ -378,6 +536,7 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                 KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
                 KeyCode::Char('K') => app.popup_scroll_mut().scroll_up(10),
                 KeyCode::Char('J') => app.popup_scroll_mut().scroll_down(10),
+                KeyCode::Char('c') => app.cycle_lookup_category(lookup)?,
                 KeyCode::Char('q') => {
                     app.selected = None;
                     app.current_lookup = None;
@@ -390,10 +549,8 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                         _ => unreachable!(),
                     };
                     if !options.is_empty() {
-                        app.current_lookup = Some(LookupResult::Success(
-                            options[app.popup_scroll().get_line() as usize].clone(),
-                        ));
-                        app.popup_scroll_mut().clear_max();
+                        let entry = options[app.popup_scroll().get_line() as usize].clone();
+                        app.open_entry(entry, lookup)?;
                     } else {
                         app.selected = None;
                         app.current_lookup = None;
@@ -409,13 +566,21 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                     KeyCode::Char('K') => app.popup_scroll_mut().scroll_up(10),
                     KeyCode::Char('J') => app.popup_scroll_mut().scroll_down(10),
                     KeyCode::Char('p') => {
-                        app.player.skills[idx as usize] = ProficiencyLevel::Normal
+                        app.player.set_skill(idx as usize, ProficiencyLevel::Normal)
+                    }
+                    KeyCode::Char('n') => {
+                        app.player.set_skill(idx as usize, ProficiencyLevel::None)
                     }
-                    KeyCode::Char('n') => app.player.skills[idx as usize] = ProficiencyLevel::None,
                     KeyCode::Char('e') => {
-                        app.player.skills[idx as usize] = ProficiencyLevel::Double
+                        app.player.set_expertise(idx as usize);
                     }
-                    KeyCode::Char('h') => app.player.skills[idx as usize] = ProficiencyLevel::Half,
+                    KeyCode::Char('h') => {
+                        app.player.set_skill(idx as usize, ProficiencyLevel::Half)
+                    }
+                    KeyCode::Char('c') => {
+                        app.player.toggle_class_skill(idx as usize);
+                    }
+                    KeyCode::Char('v') => app.show_skill_issues(),
                     KeyCode::Char('q') | KeyCode::Esc => {
                         app.selected = None;
                         app.current_lookup = None;
@@ -423,6 +588,123 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                     _ => {}
                 }
             }
+            Some(Selected::ClassSkills) => {
+                let (_, choices) = app.player.class.skill_choices(app.player.ruleset);
+                let skill = choices[app.popup_scroll().get_line() as usize];
+                match key_event.code {
+                    KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                    KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                    KeyCode::Char('K') => app.popup_scroll_mut().scroll_up(10),
+                    KeyCode::Char('J') => app.popup_scroll_mut().scroll_down(10),
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        app.player.toggle_class_skill(skill);
+                    }
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        app.selected = None;
+                        app.current_lookup = None;
+                    }
+                    _ => {}
+                }
+            }
+            Some(Selected::Training) => {
+                let category = app.index;
+                let len = app.player.proficiencies.nth(category).len();
+                match key_event.code {
+                    KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                    KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                    KeyCode::Char('h') => app.update_training_category(category.saturating_sub(1)),
+                    KeyCode::Char('l') => app.update_training_category((category + 1).min(3)),
+                    KeyCode::Char('a') => {
+                        let line = if len == 0 {
+                            0
+                        } else {
+                            app.popup_scroll().get_line() as usize + 1
+                        };
+                        app.player
+                            .proficiencies
+                            .nth_mut(category)
+                            .insert(line, String::new());
+                        app.popup_scroll_mut().set_max(len as u32 + 1);
+                        if len > 0 {
+                            app.popup_scroll_mut().scroll_down(1);
+                        }
+                        app.editing = true;
+                    }
+                    KeyCode::Char('i') | KeyCode::Enter if len > 0 => app.editing = true,
+                    KeyCode::Char('d') if len > 0 => {
+                        let line = app.popup_scroll().get_line() as usize;
+                        app.player.proficiencies.nth_mut(category).remove(line);
+                        app.popup_scroll_mut().set_max(len as u32 - 1);
+                    }
+                    KeyCode::Char('r') if category == TOOLS && len > 0 => {
+                        let tool =
+                            &app.player.proficiencies.tools[app.popup_scroll().get_line() as usize];
+                        let (roll, penalty, total) = app.player.roll_tool_check();
+                        app.last_roll = Some(if penalty > 0 {
+                            format!(
+                                "{}: {} + prof - {} exhaustion = {}",
+                                tool, roll, penalty, total
+                            )
+                        } else {
+                            format!("{}: {} + prof = {}", tool, roll, total)
+                        });
+                    }
+                    KeyCode::Char('q') | KeyCode::Esc => {
+                        app.selected = None;
+                        app.last_roll = None;
+                    }
+                    _ => {}
+                }
+            }
+            Some(Selected::Suggestions) => match key_event.code {
+                KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                KeyCode::Enter => {
+                    if let Some(suggestions) = app.detail_suggestions() {
+                        app.add_suggestion(suggestions[app.popup_scroll().get_line() as usize]);
+                    }
+                    app.selected = Some(Selected::TabItem);
+                }
+                KeyCode::Char('r') => {
+                    if let Some(suggestions) = app.detail_suggestions() {
+                        let idx = roll(suggestions.len() as u32) - 1;
+                        app.add_suggestion(suggestions[idx as usize]);
+                    }
+                    app.selected = Some(Selected::TabItem);
+                }
+                KeyCode::Char('q') | KeyCode::Esc => app.selected = Some(Selected::TabItem),
+                _ => {}
+            },
+            Some(Selected::Companions) => {
+                let line = app.popup_scroll().get_line() as usize;
+                match key_event.code {
+                    KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                    KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                    KeyCode::Enter => {
+                        app.view_companion(line.checked_sub(1));
+                        app.selected = None;
+                    }
+                    KeyCode::Char('a') => {
+                        app.player.companions.push(Companion::default());
+                        let len = app.player.companions.len() as u32 + 1;
+                        app.popup_scroll_mut().set_max(len);
+                    }
+                    KeyCode::Char('d') if line > 0 => {
+                        app.remove_companion(line - 1);
+                        let len = app.player.companions.len() as u32 + 1;
+                        app.popup_scroll_mut().set_max(len);
+                    }
+                    KeyCode::Char('w') if line > 0 => app.toggle_wild_shape(line - 1),
+                    KeyCode::Char('q') | KeyCode::Esc => app.selected = None,
+                    _ => {}
+                }
+            }
+            Some(Selected::Attacks) => match key_event.code {
+                KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
+                KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
+                KeyCode::Char('q') | KeyCode::Esc => app.selected = None,
+                _ => {}
+            },
             Some(Selected::Load) => match key_event.code {
                 KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
                 KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
@@ -432,21 +714,52 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                     app.selected = None;
                     app.current_lookup = None;
                 }
+                KeyCode::Char('i') => {
+                    app.file_buffer.clear();
+                    app.current_lookup = None;
+                    app.selected = Some(Selected::Import);
+                }
                 KeyCode::Enter => {
                     app.selected = None;
-                    app.tab_scroll_mut().reset();
                     let p = match &app.current_lookup {
                         Some(LookupResult::Files(ref f)) => {
-                            &f[app.popup_scroll().get_line() as usize]
+                            match f.get(app.popup_scroll().get_line() as usize) {
+                                Some((path, _)) => path.clone(),
+                                None => return Ok(()),
+                            }
                         }
                         _ => unreachable!(),
                     };
-                    app.load_player(PathBuf::from(p))?;
-                    app.update_tab(Tab::Notes);
                     app.current_lookup = None;
+                    app.change_player(PlayerChange::Load(p))?;
                 }
                 _ => {}
             },
+            Some(Selected::MagicItems) => {
+                let line = app.popup_scroll().get_line() as usize;
+                let item = app.player.magic_items.get_mut(line);
+                match (key_event.code, item) {
+                    (KeyCode::Char('k'), _) => app.popup_scroll_mut().scroll_up(1),
+                    (KeyCode::Char('j'), _) => app.popup_scroll_mut().scroll_down(1),
+                    (KeyCode::Char('t') | KeyCode::Enter, Some(_)) => app.toggle_attunement(),
+                    (KeyCode::Char('a'), Some(item)) => {
+                        item.charges = std::cmp::min(item.charges + 1, item.max_charges)
+                    }
+                    (KeyCode::Char('x'), Some(item)) => {
+                        item.charges = item.charges.saturating_sub(1)
+                    }
+                    (KeyCode::Char('A'), Some(item)) => item.max_charges += 1,
+                    (KeyCode::Char('X'), Some(item)) => {
+                        item.max_charges = item.max_charges.saturating_sub(1);
+                        item.charges = std::cmp::min(item.charges, item.max_charges);
+                    }
+                    (KeyCode::Char('r'), Some(item)) => {
+                        item.recharge()?;
+                    }
+                    (KeyCode::Char('q'), _) => app.selected = None,
+                    _ => {}
+                }
+            }
             Some(Selected::Error) => match key_event.code {
                 KeyCode::Char('k') => app.popup_scroll_mut().scroll_up(1),
                 KeyCode::Char('j') => app.popup_scroll_mut().scroll_down(1),
@@ -488,14 +801,43 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
                     app.popup_scroll_mut().set_max(18);
                     app.selected = Some(Selected::Proficiency)
                 }
+                KeyCode::Char('T') => {
+                    app.selected = Some(Selected::Training);
+                    app.update_training_category(0);
+                }
+                KeyCode::Char('c') => {
+                    let len = app.player.companions.len() as u32 + 1;
+                    app.popup_scroll_mut().reset();
+                    app.popup_scroll_mut().set_max(len);
+                    app.selected = Some(Selected::Companions);
+                }
+                KeyCode::Char('W') => {
+                    let len = app.attack_lines().len() as u32;
+                    app.popup_scroll_mut().reset();
+                    app.popup_scroll_mut().set_max(len);
+                    app.selected = Some(Selected::Attacks);
+                }
+                KeyCode::Char('f') => app.selected = Some(Selected::FileMenu),
+                KeyCode::Char('M') => app.show_magic_items(lookup)?,
+                KeyCode::Char('Z') => app.long_rest(),
                 KeyCode::Char('C') => app.lookup_class(lookup)?,
                 KeyCode::Char('R') => app.lookup_race(lookup)?,
+                KeyCode::Char('V') => app.show_source_issues(lookup)?,
                 KeyCode::Char('L') => {
                     app.selected = Some(Selected::FreeLookup);
                     app.popup_scroll_mut().reset();
                     app.lookup_buffer.clear();
+                    app.lookup_category = None;
                 }
                 KeyCode::Char('[') => app.lookup_files()?,
+                KeyCode::Char('r') => app.reload(lookup)?,
+                KeyCode::Char('H') => app.new_homebrew(),
+                KeyCode::Char('B') => app.show_history(),
+                KeyCode::Char('D') => app.docked = !app.docked,
+                KeyCode::Char('(') if app.docked => app.docked_scroll_mut().move_up(1),
+                KeyCode::Char(')') if app.docked => app.docked_scroll_mut().move_down(1),
+                KeyCode::Char('{') if app.docked => app.docked_scroll_mut().move_up(10),
+                KeyCode::Char('}') if app.docked => app.docked_scroll_mut().move_down(10),
                 KeyCode::Char('k') => app.tab_scroll_mut().move_up(1),
                 KeyCode::Char('j') => app.tab_scroll_mut().move_down(1),
                 KeyCode::Char('K') => app.tab_scroll_mut().move_up(10),
@@ -504,8 +846,43 @@ pub fn update(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result
             },
         }
     }
-    if app.current_tab().len() == 0 && app.editing {
+    if app.current_tab().is_empty() && app.editing && app.selected == Some(Selected::TabItem) {
         app.append_item_to_tab();
     }
     Ok(())
 }
+
+/// Process a key event in the prompt shown before closing the homebrew editor with unsaved edits.
+fn update_homebrew_discard(app: &mut App, lookup: &mut Lookup, key_event: KeyEvent) -> Result<()> {
+    match key_event.code {
+        KeyCode::Char('q' | 'n') | KeyCode::Esc => app.selected = Some(Selected::Homebrew),
+        KeyCode::Char('s') => app.selected = None,
+        KeyCode::Char('y') => {
+            app.selected = Some(Selected::Homebrew);
+            app.save_homebrew(lookup)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Process a key event in the save as, rename or import prompt.
+fn update_file_prompt(app: &mut App, key_event: KeyEvent) -> Result<()> {
+    match key_event.code {
+        KeyCode::Esc => app.selected = None,
+        KeyCode::Backspace => {
+            app.file_buffer.pop();
+        }
+        KeyCode::Char(c) => app.file_buffer.push(c),
+        KeyCode::Enter => {
+            let text = std::mem::take(&mut app.file_buffer);
+            match app.selected.take() {
+                Some(Selected::SaveAs) => app.save_player_as(&text)?,
+                Some(Selected::Import) => app.change_player(PlayerChange::Import(text))?,
+                _ => app.rename_player(&text)?,
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -5,12 +5,14 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::player::Player;
+use crate::player::{companion::Companion, Player};
 
 /// A widget to display the player info bar.
 pub struct InfoBar<'a> {
     /// The bound player.
     player: &'a Player,
+    /// The companion to show in place of the player, if any.
+    companion: Option<&'a Companion>,
     /// Which item to highlight, if any.
     highlight: Option<(u8, Color)>,
     fg: Color,
@@ -22,6 +24,7 @@ impl<'a> InfoBar<'a> {
     pub fn new(player: &'a Player) -> Self {
         Self {
             player,
+            companion: None,
             highlight: None,
             fg: Color::Yellow,
             bg: Color::Black,
@@ -34,6 +37,12 @@ impl<'a> InfoBar<'a> {
         self
     }
 
+    /// Show the given companion in place of the player, if any.
+    pub fn companion(mut self, companion: Option<&'a Companion>) -> Self {
+        self.companion = companion;
+        self
+    }
+
     /// Set the foreground color of the widget
     pub fn fg(mut self, color: Color) -> Self {
         self.fg = color;
@@ -53,6 +62,55 @@ impl<'a> InfoBar<'a> {
 
 impl<'a> Widget for InfoBar<'a> {
     fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let mut line = match self.companion {
+            Some(companion) => self.companion_line(companion),
+            None => self.player_line(),
+        };
+
+        if let Some((item, color)) = self.highlight {
+            // Actual item and '|' separator make stride of 2.
+            line.spans[item as usize * 2].patch_style(Style::default().fg(self.bg).bg(color));
+        }
+
+        Paragraph::new(vec![line])
+            .block(
+                Block::new()
+                    .title("Info (i)")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.fg)),
+            )
+            .alignment(Alignment::Left)
+            .render(area, buf);
+    }
+}
+
+impl<'a> InfoBar<'a> {
+    fn companion_line(&self, companion: &Companion) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("Name: {}", companion.name), self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled(format!("HP: {}", companion.hp), self.style()),
+            Span::styled("/", self.style()),
+            Span::styled(format!("{}", companion.max_hp), self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled(format!("Temp HP: {}", companion.temp_hp), self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled(format!("AC: {}", companion.ac), self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled(format!("Kind: {}", companion.kind), self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled("(c)ompanions", self.style()),
+        ])
+    }
+
+    fn player_line(&self) -> Line<'a> {
+        // Backgrounds with no counterpart in the player's ruleset are kept, but marked
+        let background = if self.player.background.is_available(self.player.ruleset) {
+            self.player.background.to_string()
+        } else {
+            format!("{} (not {})", self.player.background, self.player.ruleset)
+        };
         let mut line = Line::from(vec![
             Span::styled(format!("HP: {}", self.player.hp), self.style()),
             Span::styled("/", self.style()),
@@ -72,30 +130,37 @@ impl<'a> Widget for InfoBar<'a> {
                 self.style(),
             ),
             Span::styled(" | ", self.style()),
+            Span::styled(format!("Background: {}", background), self.style()),
+            Span::styled(" | ", self.style()),
             Span::styled(
-                format!("Background: {}", self.player.background),
+                format!("Exhaustion: {}", self.player.exhaustion),
                 self.style(),
             ),
             Span::styled(" | ", self.style()),
             Span::styled("(F)unds", self.style()),
             Span::styled(" | ", self.style()),
             Span::styled("(P)roficiencies", self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled("(T)raining", self.style()),
+            Span::styled(" | ", self.style()),
+            Span::styled("(c)ompanions", self.style()),
         ]);
 
-        if let Some((item, color)) = self.highlight {
-            // Actual item and '|' separator make stride of 2.
-            line.spans[item as usize * 2].patch_style(Style::default().fg(self.bg).bg(color));
+        if let Some(form) = self.player.wild_shape_form() {
+            line.spans.push(Span::styled(" | ", self.style()));
+            line.spans.push(Span::styled(
+                format!("Wild Shape: {}", form.name),
+                self.style(),
+            ));
         }
 
-        Paragraph::new(vec![line])
-            .block(
-                Block::new()
-                    .title("Info (i)")
-                    .title_alignment(Alignment::Center)
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(self.fg)),
-            )
-            .alignment(Alignment::Left)
-            .render(area, buf);
+        let feats = self.player.origin_feats();
+        if !feats.is_empty() {
+            line.spans.push(Span::styled(" | ", self.style()));
+            line.spans
+                .push(Span::styled(format!("Feats: {}", feats.join(", ")), self.style()));
+        }
+
+        line
     }
 }
@@ -5,7 +5,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::player::Player;
+use crate::player::{ruleset::Ruleset, Player};
 
 /// A widget that renders the top player bar.
 pub struct PlayerBar<'a> {
@@ -13,6 +13,8 @@ pub struct PlayerBar<'a> {
     player: &'a Player,
     /// Which item to highlight, if any.
     highlight: Option<(u8, Color)>,
+    /// Whether the player has unsaved changes, marked in the title.
+    modified: bool,
     fg: Color,
     bg: Color,
 }
@@ -23,6 +25,7 @@ impl<'a> PlayerBar<'a> {
         Self {
             player,
             highlight: None,
+            modified: false,
             fg: Color::Yellow,
             bg: Color::Black,
         }
@@ -34,6 +37,12 @@ impl<'a> PlayerBar<'a> {
         self
     }
 
+    /// Set whether the player has unsaved changes.
+    pub fn modified(mut self, modified: bool) -> Self {
+        self.modified = modified;
+        self
+    }
+
     /// Set the foreground color of the widget
     pub fn fg(mut self, color: Color) -> Self {
         self.fg = color;
@@ -53,10 +62,22 @@ impl<'a> PlayerBar<'a> {
 
 impl<'a> Widget for PlayerBar<'a> {
     fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        // Races with no counterpart in the player's ruleset are kept, but marked
+        let race = if self.player.race.is_available(self.player.ruleset) {
+            self.player.race.to_string()
+        } else {
+            format!("{} (not {})", self.player.race, self.player.ruleset)
+        };
         let mut items = vec![
             Span::styled(format!("Name: {: <5}", self.player.name), self.style()),
             Span::styled(" | ", self.style()),
-            Span::styled(format!("(R)ace: {: <10}", self.player.race), self.style()),
+            Span::styled(
+                match self.player.ruleset {
+                    Ruleset::Phb2014 => format!("(R)ace: {: <10}", race),
+                    Ruleset::Phb2024 => format!("Species (R): {: <10}", race),
+                },
+                self.style(),
+            ),
             Span::styled(" | ", self.style()),
             Span::styled(format!("Level: {: <2}", self.player.level), self.style()),
             Span::styled(" | ", self.style()),
@@ -66,6 +87,8 @@ impl<'a> Widget for PlayerBar<'a> {
                 format!("Alignment: {: <2}", self.player.alignment),
                 self.style(),
             ),
+            Span::styled(" | ", self.style()),
+            Span::styled(format!("Rules: {}", self.player.ruleset), self.style()),
         ];
 
         if let Some((item, color)) = self.highlight {
@@ -73,10 +96,16 @@ impl<'a> Widget for PlayerBar<'a> {
             items[item as usize * 2].patch_style(Style::default().fg(Color::Black).bg(color));
         }
 
+        let title = if self.modified {
+            "Player Sheet (u) [+]"
+        } else {
+            "Player Sheet (u)"
+        };
+
         Paragraph::new(vec![Line::from(items)])
             .block(
                 Block::new()
-                    .title("Player Sheet (u)")
+                    .title(title)
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(self.fg)),
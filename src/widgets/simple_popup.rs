@@ -1,6 +1,7 @@
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    text::Text,
     widgets::{Block, Paragraph, Widget, Wrap},
 };
 
@@ -8,7 +9,7 @@ use super::PopupSize;
 
 /// A simple popup menu that displays just a string
 pub struct SimplePopup<'a> {
-    text: &'a String,
+    text: Text<'a>,
     size: PopupSize,
     fg: Color,
     bg: Color,
@@ -20,9 +21,14 @@ pub struct SimplePopup<'a> {
 
 impl<'a> SimplePopup<'a> {
     /// Create a new [`SimplePopup`] with the given size and the given data
-    pub fn new(data: &'a String, size: PopupSize) -> Self {
+    pub fn new(data: &'a str, size: PopupSize) -> Self {
+        Self::styled(Text::from(data), size)
+    }
+
+    /// Create a new [`SimplePopup`] with the given size and the given styled text
+    pub fn styled(text: Text<'a>, size: PopupSize) -> Self {
         Self {
-            text: data,
+            text,
             size,
             fg: Color::White,
             bg: Color::Black,
@@ -134,8 +140,9 @@ impl<'a> Widget for SimplePopup<'a> {
             buf.set_string(chunk.x, y, &clear_string, self.style())
         }
 
-        let mut p = Paragraph::new(self.text.clone())
-            .style(self.style())
+        let style = self.style();
+        let mut p = Paragraph::new(self.text)
+            .style(style)
             .block(self.block.unwrap_or_default())
             .scroll((self.scroll as u16, 0))
             .alignment(self.alignment);
@@ -16,6 +16,8 @@ pub struct StatBlock<'a> {
     stats: &'a Stats,
     /// Which stat to highlight, if any.
     highlight: Option<(u8, Color)>,
+    /// The title of the block.
+    title: String,
     fg: Color,
     bg: Color,
 }
@@ -26,6 +28,7 @@ impl<'a> StatBlock<'a> {
         Self {
             stats,
             highlight: None,
+            title: String::from("Stats"),
             fg: Color::Yellow,
             bg: Color::Black,
         }
@@ -37,6 +40,12 @@ impl<'a> StatBlock<'a> {
         self
     }
 
+    /// Set the title of the block.
+    pub fn title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
     /// Set the foreground color of the widget
     pub fn fg(mut self, color: Color) -> Self {
         self.fg = color;
@@ -85,7 +94,7 @@ impl<'a> Widget for StatBlock<'a> {
         Paragraph::new(lines)
             .block(
                 Block::new()
-                    .title("Stats")
+                    .title(self.title)
                     .title_alignment(Alignment::Center)
                     .title(Title::from("(s)").position(Position::Bottom))
                     .borders(Borders::ALL)
@@ -5,7 +5,10 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::{core::Tab, player::Player};
+use crate::{
+    core::Tab,
+    player::{companion::Companion, details::DETAIL_NAMES, Player},
+};
 
 use super::text_edit::TextEdit;
 
@@ -15,6 +18,10 @@ pub struct TabPanel<'a> {
     player: &'a Player,
     /// The tab to display
     tab: Tab,
+    /// The detail field to display in the details tab.
+    detail_field: usize,
+    /// The companion whose attacks are shown in place of the tab, if any.
+    companion: Option<&'a Companion>,
     /// Which line to highlight, if any.
     highlight: Option<((u16, u16), Color)>,
     /// The amount of lines to scroll the viewport by.
@@ -29,6 +36,8 @@ impl<'a> TabPanel<'a> {
         Self {
             player,
             tab,
+            detail_field: 0,
+            companion: None,
             highlight: None,
             scroll: 0,
             fg: Color::Yellow,
@@ -43,6 +52,18 @@ impl<'a> TabPanel<'a> {
         self
     }
 
+    /// Set the detail field shown in the details tab.
+    pub fn detail_field(mut self, field: usize) -> Self {
+        self.detail_field = field;
+        self
+    }
+
+    /// Show the attacks of the given companion in place of the tab, if any.
+    pub fn companion(mut self, companion: Option<&'a Companion>) -> Self {
+        self.companion = companion;
+        self
+    }
+
     /// Set the amount of scroll lines.
     pub fn scroll(mut self, scroll: u16) -> Self {
         self.scroll = scroll;
@@ -92,10 +113,18 @@ impl<'a> Widget for TabPanel<'a> {
         let scroll_chunk = bottom_chunks[1];
 
         // Draw the main tab pane
-        let tab = match self.tab {
-            Tab::Notes => &self.player.notes,
-            Tab::Inventory => &self.player.inventory,
-            Tab::Spells => &self.player.spells,
+        let tab = match (self.companion, self.tab) {
+            (Some(companion), _) => &companion.attacks,
+            (None, Tab::Notes) => &self.player.notes,
+            (None, Tab::Inventory) => &self.player.inventory,
+            (None, Tab::Spells) => &self.player.spells,
+            (None, Tab::Details) => self.player.details.nth(self.detail_field),
+        };
+
+        let title = match (self.companion, self.tab) {
+            (Some(companion), _) => format!("{}: Attacks (t)", companion.name),
+            (None, Tab::Details) => format!("Details: {} (t)", DETAIL_NAMES[self.detail_field]),
+            _ => format!("{} (t)", self.tab),
         };
 
         let mut tab_view = TextEdit::from(tab.as_slice())
@@ -105,7 +134,7 @@ impl<'a> Widget for TabPanel<'a> {
             .scroll_to(self.scroll as u32)
             .block(
                 Block::default()
-                    .title(format!("{} (t)", self.tab))
+                    .title(title)
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(self.fg)),
             );
@@ -124,12 +153,15 @@ impl<'a> Widget for TabPanel<'a> {
             Span::styled("INVENTORY", Style::default().fg(self.select).bg(self.bg)),
             Span::from("   "),
             Span::styled("SP(E)LLS", Style::default().fg(self.select).bg(self.bg)),
+            Span::from("   "),
+            Span::styled("DETAILS", Style::default().fg(self.select).bg(self.bg)),
         ];
 
         let idx = match self.tab {
             Tab::Notes => 0,
             Tab::Inventory => 2,
             Tab::Spells => 4,
+            Tab::Details => 6,
         };
 
         text[idx].patch_style(
@@ -89,7 +89,7 @@ where
         p.render(area, buf);
 
         if let Some(((line, col), color)) = self.highlight {
-            buf.get_mut(col as u16 + area.x + 1, line.saturating_sub(self.scroll as u16) + area.y + 1).set_bg(color).set_fg(self.bg);
+            buf.get_mut(col + area.x + 1, line.saturating_sub(self.scroll as u16) + area.y + 1).set_bg(color).set_fg(self.bg);
         }
     }
 }
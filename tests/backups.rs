@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use scrim::core::{backups, App};
+
+/// Create an empty directory for a test to save players to.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("scrim-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn saves_rotate_backups() {
+    let dir = test_dir("backups-rotate");
+    let path = dir.join("tordek.yaml");
+
+    for level in 1..=5 {
+        let data = format!("name: Tordek\nlevel: {}\n", level);
+        backups::backup(&path, &data, 3).unwrap();
+        backups::write_atomic(&path, &data).unwrap();
+        // Backups are named to the millisecond
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    let list = backups::list(&path);
+    assert_eq!(list.len(), 3);
+    let newest = std::fs::read_to_string(&list[0].path).unwrap();
+    assert_eq!(newest, "name: Tordek\nlevel: 4\n");
+    let oldest = std::fs::read_to_string(&list[2].path).unwrap();
+    assert_eq!(oldest, "name: Tordek\nlevel: 2\n");
+
+    // No temporary files are left behind
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(files, 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unchanged_saves_are_not_backed_up() {
+    let dir = test_dir("backups-unchanged");
+    let path = dir.join("tordek.json");
+
+    for _ in 0..3 {
+        backups::backup(&path, "{}", 5).unwrap();
+        backups::write_atomic(&path, "{}").unwrap();
+    }
+    assert!(backups::list(&path).is_empty());
+
+    backups::backup(&path, "{\"level\":2}", 0).unwrap();
+    assert!(backups::list(&path).is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn renamed_players_keep_their_backups() {
+    let dir = test_dir("backups-rename");
+    let mut app = App::new();
+    app.player.name = String::from("Tordek");
+    app.save_player_as(dir.join("Tordek.yaml").to_str().unwrap())
+        .unwrap();
+    app.player.level = 2;
+    app.save_player().unwrap();
+    assert_eq!(backups::list(&dir.join("Tordek.yaml")).len(), 1);
+
+    app.rename_player("Tordek Stonehelm").unwrap();
+    let path = dir.join("Tordek Stonehelm.yaml");
+    assert_eq!(app.path.as_ref(), Some(&path));
+    assert!(backups::list(&dir.join("Tordek.yaml")).is_empty());
+    assert_eq!(backups::list(&path).len(), 1);
+
+    // Restoring a backup from before the rename keeps the current file
+    app.show_backups().unwrap();
+    app.restore_backup().unwrap();
+    assert_eq!(app.path.as_ref(), Some(&path));
+    assert_eq!(app.player.name, "Tordek");
+    assert!(!dir.join("Tordek.yaml").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn diff_shows_changed_lines() {
+    let old = "name: Tordek\nlevel: 3\nclass: Fighter\n";
+    let new = "name: Tordek\nlevel: 4\nclass: Fighter\n";
+    assert_eq!(
+        backups::diff(old, new),
+        [
+            "  name: Tordek",
+            "- level: 3",
+            "+ level: 4",
+            "  class: Fighter"
+        ]
+    );
+    assert!(backups::diff(old, old).is_empty());
+}
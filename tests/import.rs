@@ -0,0 +1,327 @@
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use scrim::{
+    import::import,
+    lookup::{Lookup, LookupEntry},
+    metadata::Metadata,
+};
+use serde_json::{json, Value};
+
+/// Create an empty directory for a test to import lookups into.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("scrim-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("lookups")).unwrap();
+    dir
+}
+
+/// Import `data` from a file with the given name, then load the result as a lookup.
+fn import_lookup(dir: &Path, file: &str, data: &Value) -> Lookup {
+    let input = dir.join(file);
+    std::fs::write(&input, serde_json::to_string(data).unwrap()).unwrap();
+    let output = dir.join("lookups").join("imported.yaml");
+    import(&input, &output, false).unwrap();
+
+    let mut lookup = Lookup::new(dir.join("lookups"));
+    lookup.load().unwrap();
+    assert!(lookup.take_diagnostics().is_empty());
+    lookup
+}
+
+fn entry(lookup: &mut Lookup, name: &str) -> Rc<LookupEntry> {
+    lookup.get_entry(name).unwrap().cloned().unwrap()
+}
+
+fn five_e_tools() -> Value {
+    json!({
+        "spell": [{
+            "name": "Fireball",
+            "source": "PHB",
+            "level": 3,
+            "school": "V",
+            "time": [{ "number": 1, "unit": "action" }],
+            "range": { "type": "point", "distance": { "type": "feet", "amount": 150 } },
+            "components": { "v": true, "s": true, "m": "a tiny ball of bat guano and sulfur" },
+            "duration": [{ "type": "instant" }],
+            "entries": ["Each creature in the sphere takes {@damage 8d6} fire damage."],
+            "entriesHigherLevel": [{
+                "type": "entries",
+                "name": "At Higher Levels",
+                "entries": ["The damage increases by {@scaledamage 8d6|3-9|1d6} for each slot level above 3rd."]
+            }],
+            "classes": { "fromClassList": [{ "name": "Sorcerer" }, { "name": "Wizard" }] }
+        }],
+        "baseitem": [
+            {
+                "name": "Longsword",
+                "source": "PHB",
+                "type": "M",
+                "weaponCategory": "martial",
+                "dmg1": "1d8",
+                "dmgType": "S",
+                "dmg2": "1d10",
+                "property": ["V"],
+                "value": 1500,
+                "weight": 3
+            },
+            {
+                "name": "Chain Mail",
+                "source": "PHB",
+                "type": "HA",
+                "ac": 16,
+                "strength": "13",
+                "stealth": true,
+                "value": 7500,
+                "weight": 55
+            }
+        ],
+        "item": [{
+            "name": "Wand of Magic Missiles",
+            "source": "DMG",
+            "type": "WD",
+            "rarity": "uncommon",
+            "charges": 7,
+            "recharge": "dawn",
+            "rechargeAmount": "{@dice 1d6 + 1}",
+            "entries": ["While holding it, you can cast {@spell magic missile} from it."]
+        }],
+        "monster": [{
+            "name": "Goblin",
+            "source": "MM",
+            "size": ["S"],
+            "type": { "type": "humanoid", "tags": ["goblinoid"] },
+            "alignment": ["N", "E"],
+            "ac": [{ "ac": 15, "from": ["{@item leather armor|phb}", "{@item shield|phb}"] }],
+            "hp": { "average": 7, "formula": "2d6" },
+            "speed": { "walk": 30, "climb": 20 },
+            "str": 8,
+            "dex": 14,
+            "con": 10,
+            "int": 10,
+            "wis": 8,
+            "cha": 8,
+            "skill": { "stealth": "+6" },
+            "senses": ["darkvision 60 ft."],
+            "passive": 9,
+            "languages": ["Common", "Goblin"],
+            "cr": "1/4",
+            "trait": [{
+                "name": "Nimble Escape",
+                "entries": ["The goblin can take the Disengage or Hide action as a bonus action."]
+            }],
+            "action": [{
+                "name": "Scimitar",
+                "entries": ["{@atk mw} {@hit 4} to hit, reach 5 ft. {@h}5 ({@damage 1d6 + 2}) slashing damage."]
+            }]
+        }]
+    })
+}
+
+#[test]
+fn imports_5etools_spells() {
+    let dir = test_dir("import-5etools-spell");
+    let mut lookup = import_lookup(&dir, "phb.json", &five_e_tools());
+
+    let fireball = entry(&mut lookup, "fireball");
+    assert_eq!(fireball.category, "spell");
+    assert_eq!(fireball.source, "PHB");
+    assert_eq!(fireball.description_short, "3rd-level evocation");
+    assert!(fireball
+        .description
+        .starts_with("**Casting Time:** 1 action\n**Range:** 150 feet"));
+    assert!(fireball.description.contains("takes 8d6 fire damage"));
+    assert!(fireball
+        .description
+        .contains("**At Higher Levels.** The damage increases by 1d6"));
+    let Some(Metadata::Spell(spell)) = &fireball.metadata else {
+        panic!("fireball has no spell metadata");
+    };
+    assert_eq!(spell.level, 3);
+    assert_eq!(spell.school, "Evocation");
+    assert_eq!(spell.casting_time, "1 action");
+    assert_eq!(spell.range, "150 feet");
+    assert_eq!(
+        spell.components,
+        "V, S, M (a tiny ball of bat guano and sulfur)"
+    );
+    assert_eq!(spell.duration, "Instantaneous");
+    assert!(!spell.ritual && !spell.concentration);
+    assert_eq!(spell.classes, ["Sorcerer", "Wizard"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn imports_5etools_items() {
+    let dir = test_dir("import-5etools-items");
+    let mut lookup = import_lookup(&dir, "items.json", &five_e_tools());
+
+    let longsword = entry(&mut lookup, "longsword");
+    assert_eq!(longsword.category, "weapon");
+    assert_eq!(longsword.description_short, "Martial melee weapon");
+    let Some(Metadata::Weapon(weapon)) = &longsword.metadata else {
+        panic!("longsword has no weapon metadata");
+    };
+    assert_eq!(weapon.weapon_category, "martial");
+    assert_eq!(weapon.damage, "1d8 slashing");
+    assert_eq!(weapon.properties, ["versatile (1d10)"]);
+    assert_eq!(weapon.cost, "15 gp");
+    assert_eq!(weapon.weight, "3 lb.");
+    assert!(weapon.rarity.is_empty());
+
+    let chain_mail = entry(&mut lookup, "chain mail");
+    assert_eq!(chain_mail.category, "armor");
+    let Some(Metadata::Armor(armor)) = &chain_mail.metadata else {
+        panic!("chain mail has no armor metadata");
+    };
+    assert_eq!(armor.armor_category, "Heavy armor");
+    assert_eq!(armor.ac, 16);
+    assert_eq!(armor.strength, 13);
+    assert!(armor.stealth_disadvantage);
+    assert_eq!(armor.cost, "75 gp");
+
+    let wand = entry(&mut lookup, "wand of magic missiles");
+    assert_eq!(wand.category, "magic item");
+    assert_eq!(wand.source, "DMG");
+    assert_eq!(wand.description_short, "Wand, uncommon");
+    assert!(wand.description.contains("you can cast [[magic missile]]"));
+    let Some(Metadata::Item(item)) = &wand.metadata else {
+        panic!("the wand has no item metadata");
+    };
+    assert_eq!(item.rarity, "uncommon");
+    assert_eq!(item.charges, 7);
+    assert_eq!(item.recharge, "1d6 + 1 at dawn");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn imports_5etools_monsters() {
+    let dir = test_dir("import-5etools-monster");
+    let mut lookup = import_lookup(&dir, "bestiary.json", &five_e_tools());
+
+    let goblin = entry(&mut lookup, "goblin");
+    assert_eq!(goblin.category, "monster");
+    assert_eq!(goblin.source, "MM");
+    assert_eq!(goblin.description_short, "Small humanoid, challenge 1/4");
+    let Some(Metadata::Monster(monster)) = &goblin.metadata else {
+        panic!("the goblin has no monster metadata");
+    };
+    assert_eq!(monster.size, "Small");
+    assert_eq!(monster.creature_type, "humanoid");
+    assert_eq!(monster.alignment, "neutral evil");
+    assert_eq!((monster.ac, monster.hp), (15, 7));
+    assert_eq!(monster.hit_dice, "2d6");
+    assert_eq!(monster.speed, "30 ft., climb 20 ft.");
+    assert_eq!(
+        [
+            monster.str,
+            monster.dex,
+            monster.con,
+            monster.int,
+            monster.wis,
+            monster.cha
+        ],
+        [8, 14, 10, 10, 8, 8]
+    );
+    assert_eq!(monster.skills, "Stealth +6");
+    assert_eq!(monster.senses, "darkvision 60 ft., passive Perception 9");
+    assert_eq!(monster.languages, "Common, Goblin");
+    assert_eq!(monster.cr, "1/4");
+    assert_eq!(monster.traits.len(), 1);
+    assert_eq!(monster.traits[0].name, "Nimble Escape");
+    assert_eq!(monster.actions.len(), 1);
+    assert_eq!(monster.actions[0].name, "Scimitar");
+    assert_eq!(
+        monster.actions[0].description,
+        "*Melee Weapon Attack:* +4 to hit, reach 5 ft. *Hit:* 5 (1d6 + 2) slashing damage."
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn imports_open5e_spells() {
+    let dir = test_dir("import-open5e-spell");
+    let data = json!({
+        "results": [{
+            "name": "Shield",
+            "desc": "An invisible barrier of magical force appears and protects you.",
+            "higher_level": "",
+            "range": "Self",
+            "components": "V, S",
+            "material": "",
+            "ritual": "no",
+            "duration": "1 round",
+            "concentration": "no",
+            "casting_time": "1 reaction",
+            "level": "1st-level",
+            "level_int": 1,
+            "school": "abjuration",
+            "dnd_class": "Sorcerer, Wizard",
+            "document__title": "Systems Reference Document"
+        }]
+    });
+    let mut lookup = import_lookup(&dir, "spells.json", &data);
+
+    let shield = entry(&mut lookup, "shield");
+    assert_eq!(shield.category, "spell");
+    assert_eq!(shield.source, "Systems Reference Document");
+    assert_eq!(shield.description_short, "1st-level abjuration");
+    assert!(shield
+        .description
+        .ends_with("\n\nAn invisible barrier of magical force appears and protects you."));
+    let Some(Metadata::Spell(spell)) = &shield.metadata else {
+        panic!("shield has no spell metadata");
+    };
+    assert_eq!(spell.level, 1);
+    assert_eq!(spell.school, "Abjuration");
+    assert_eq!(spell.casting_time, "1 reaction");
+    assert_eq!(spell.range, "Self");
+    assert_eq!(spell.components, "V, S");
+    assert_eq!(spell.duration, "1 round");
+    assert!(!spell.ritual && !spell.concentration);
+    assert_eq!(spell.classes, ["Sorcerer", "Wizard"]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn duplicates_are_skipped_and_reported() {
+    let dir = test_dir("import-duplicates");
+    let spell = json!({ "name": "Light", "source": "PHB", "level": 0, "school": "V" });
+    let mut reprint = spell.clone();
+    reprint["source"] = json!("XPHB");
+    let data = json!({ "spell": [spell, spell, reprint, reprint] });
+    let input = dir.join("spells.json");
+    std::fs::write(&input, data.to_string()).unwrap();
+
+    let (count, skipped) = import(&input, &dir.join("spells.yaml"), false).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(skipped.len(), 2);
+    assert!(skipped[0].starts_with("skipped 'Light' from PHB"));
+    assert!(skipped[1].starts_with("skipped 'Light' from XPHB"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn existing_output_needs_force() {
+    let dir = test_dir("import-force");
+    let input = dir.join("spells.json");
+    std::fs::write(&input, five_e_tools().to_string()).unwrap();
+    let output = dir.join("spells.yaml");
+    std::fs::write(&output, "hand edits").unwrap();
+
+    assert!(import(&input, &output, false).is_err());
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "hand edits");
+    import(&input, &output, true).unwrap();
+    assert!(std::fs::read_to_string(&output)
+        .unwrap()
+        .contains("fireball"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
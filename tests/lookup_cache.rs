@@ -0,0 +1,211 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use scrim::lookup::Lookup;
+
+const WORDS: [&str; 16] = [
+    "fire",
+    "cold",
+    "creature",
+    "saving",
+    "throw",
+    "damage",
+    "radius",
+    "sphere",
+    "range",
+    "target",
+    "spell",
+    "level",
+    "slot",
+    "action",
+    "bonus",
+    "concentration",
+];
+
+/// Create an empty directory for a test to write lookups to.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("scrim-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("lookups")).unwrap();
+    dir
+}
+
+/// Write `files` JSON lookup files of `entries` generated entries each.
+fn generate_lookups(dir: &Path, files: usize, entries: usize) {
+    for file in 0..files {
+        let entries: serde_json::Map<String, serde_json::Value> = (0..entries)
+            .map(|i| {
+                let name = format!("Entry {} {}", file, i);
+                let description: Vec<&str> = (0..80)
+                    .map(|w| WORDS[(file * 7 + i * 3 + w * 5) % WORDS.len()])
+                    .collect();
+                let entry = serde_json::json!({
+                    "name": name,
+                    "description_short": "Source: Player's Handbook",
+                    "description": description.join(" "),
+                    "spell": { "level": i % 10, "school": "Evocation" },
+                });
+                (name.to_lowercase(), entry)
+            })
+            .collect();
+        let lookup = serde_json::json!({ "entries": entries });
+        std::fs::write(
+            dir.join(format!("spells-{}.json", file)),
+            serde_json::to_string(&lookup).unwrap(),
+        )
+        .unwrap();
+    }
+}
+
+/// Load a lookup and time how long it took.
+fn timed_load(lookup: &mut Lookup) -> Duration {
+    let start = Instant::now();
+    lookup.load().unwrap();
+    start.elapsed()
+}
+
+#[test]
+fn cached_lookup_matches_parsed_lookup() {
+    let dir = test_dir("cache-matches");
+    let lookups = dir.join("lookups");
+    let cache = dir.join("lookups.cache");
+    generate_lookups(&lookups, 4, 100);
+
+    let mut uncached = Lookup::new(lookups.clone());
+    uncached.load().unwrap();
+
+    let mut building = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    building.load().unwrap();
+    assert!(cache.exists());
+
+    let mut cached = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    cached.load().unwrap();
+
+    // The cached lookup must give the same results as parsing
+    let entry = cached.get_entry("entry 3 42").unwrap().unwrap().clone();
+    assert_eq!(entry.name, "Entry 3 42");
+    assert_eq!(entry.source, "Player's Handbook");
+    assert!(entry.matches(&[("level".into(), "2".into())]));
+
+    let names = |entries: Vec<std::rc::Rc<scrim::lookup::LookupEntry>>| -> Vec<String> {
+        entries.iter().map(|e| e.name.clone()).collect()
+    };
+    assert_eq!(
+        names(cached.search_descriptions("fire sph").unwrap()),
+        names(uncached.search_descriptions("fire sph").unwrap())
+    );
+    assert_eq!(
+        names(cached.get_completions("entry 3 1").unwrap()),
+        names(uncached.get_completions("entry 3 1").unwrap())
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unchanged_fingerprint_uses_cache() {
+    let dir = test_dir("cache-hit");
+    let lookups = dir.join("lookups");
+    let cache = dir.join("lookups.cache");
+    let file = lookups.join("fruit.yaml");
+    std::fs::write(
+        &file,
+        "entries:\n  apple:\n    name: Apple\n    description: Red.\n",
+    )
+    .unwrap();
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    let modified = std::fs::metadata(&file).unwrap().modified().unwrap();
+
+    // Same length and modification time, so only a cache hit still sees the old text
+    std::fs::write(
+        &file,
+        "entries:\n  apple:\n    name: Apple\n    description: Tan.\n",
+    )
+    .unwrap();
+    let set_modified = |time: SystemTime| {
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(time)
+            .unwrap()
+    };
+    set_modified(modified);
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    let entry = lookup.get_entry("apple").unwrap().unwrap().clone();
+    assert_eq!(entry.description, "Red.");
+
+    // A new modification time changes the fingerprint, so the file is parsed again
+    set_modified(modified + Duration::from_secs(10));
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    let entry = lookup.get_entry("apple").unwrap().unwrap().clone();
+    assert_eq!(entry.description, "Tan.");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Compare load times with and without the cache.
+///
+/// Run with `cargo test --release -- --ignored` to see the timings.
+#[test]
+#[ignore]
+fn cached_load_is_faster_than_parsing() {
+    let dir = test_dir("cache-bench");
+    let lookups = dir.join("lookups");
+    let cache = dir.join("lookups.cache");
+    generate_lookups(&lookups, 10, 1000);
+
+    let mut uncached = Lookup::new(lookups.clone());
+    let parse_time = timed_load(&mut uncached);
+
+    let mut building = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    let build_time = timed_load(&mut building);
+
+    let mut cached = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    let cached_time = timed_load(&mut cached);
+
+    eprintln!(
+        "10000 entries: parsed in {:?}, built cache in {:?}, loaded cache in {:?}",
+        parse_time, build_time, cached_time
+    );
+    assert!(cached_time < parse_time);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn changed_files_invalidate_cache() {
+    let dir = test_dir("cache-invalidate");
+    let lookups = dir.join("lookups");
+    let cache = dir.join("lookups.cache");
+    generate_lookups(&lookups, 2, 10);
+
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    assert!(lookup.get_entry("homebrew").unwrap().is_none());
+
+    std::fs::write(
+        lookups.join("homebrew.yaml"),
+        "entries:\n  homebrew:\n    name: Homebrew\n    description: Made at home.\n",
+    )
+    .unwrap();
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    assert!(lookup.get_entry("homebrew").unwrap().is_some());
+
+    // Problems found while parsing are reported again when the cache is used
+    std::fs::write(lookups.join("broken.json"), "{ not json").unwrap();
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    assert_eq!(lookup.take_diagnostics().len(), 1);
+    let mut lookup = Lookup::new(lookups.clone()).with_cache(cache.clone());
+    lookup.load().unwrap();
+    assert_eq!(lookup.take_diagnostics().len(), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}